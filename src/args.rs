@@ -1,19 +1,151 @@
+use clap::builder::TypedValueParser;
+
 pub fn argument_parser() -> clap::ArgMatches {
-    return clap::command!()
+    clap::command!()
         // info
         .about("Pokemon Colorscripts written in Rust")
         .author("Vomitblood")
+        // global/profile
+        .arg(
+            clap::Arg::new("profile")
+                .help("Use a profile from the config file for default options")
+                .long("profile")
+                .global(true),
+        )
+        // global/color
+        .arg(
+            clap::Arg::new("color")
+                .help("When to use colors. `auto` checks for a terminal, TERM=dumb, NO_COLOR and CLICOLOR_FORCE. `never` prints plain glyphs.")
+                .long("color")
+                .default_value("auto")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["auto", "always", "never"])
+                        .map(|s| s.parse::<crate::structs::ColorMode>().unwrap()),
+                )
+                .global(true),
+        )
+        // global/color-depth
+        .arg(
+            clap::Arg::new("color-depth")
+                .help("Number of colors the terminal can show. `auto` checks COLORTERM and TERM. Colorscripts are reduced to fit.")
+                .long("color-depth")
+                .default_value("auto")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["auto", "truecolor", "256", "16"])
+                        .map(|s| s.parse::<crate::structs::ColorDepth>().unwrap()),
+                )
+                .global(true),
+        )
+        // global/dither
+        .arg(
+            clap::Arg::new("dither")
+                .help("Use ordered dithering when reducing colors for 256 or 16 color terminals")
+                .long("dither")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .after_help(
+            "Tip: Defaults for `print` and `fetch` can be set in ~/.config/rustmon/config.toml!
+Command line flags always take priority. Do `rustmon config show` to see the effective settings.",
+        )
+        // config subcommand
+        .subcommand(
+            clap::Command::new("config")
+                .about("Inspect the config file")
+                .subcommand(
+                    clap::Command::new("show")
+                        .about("Print the effective settings and where each one came from"),
+                )
+                .subcommand_required(true),
+        )
+        // export subcommand
+        .subcommand(
+            print_command()
+                .name("export")
+                .about("Export Pokemon colorscripts as a file")
+                // export/all-forms
+                .arg(
+                    clap::Arg::new("all-forms")
+                        .help("Put every form on the sheet, not only the regular ones")
+                        .long("all-forms")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("sheet"),
+                )
+                // export/format
+                .arg(
+                    clap::Arg::new("format")
+                        .help("Format of the file. `html` keeps the half-block look in a `<pre>`, `svg` draws every pixel as a square, `png` is an image scaled up by `--scale`.")
+                        .required(true)
+                        .value_parser(
+                            clap::builder::PossibleValuesParser::new(["html", "svg", "png"])
+                                .map(|s| s.parse::<crate::structs::Format>().unwrap()),
+                        ),
+                )
+                // export/output
+                .arg(
+                    clap::Arg::new("output")
+                        .help("File to write to. If not specified, the file is written to stdout.")
+                        .short('o')
+                        .long("output"),
+                )
+                // export/sheet
+                .arg(
+                    clap::Arg::new("sheet")
+                        .help("Tile every Pokemon into one labeled sheet. Use `--columns` to pick the number per row.")
+                        .long("sheet")
+                        .action(clap::ArgAction::SetTrue)
+                        .conflicts_with_all(["name", "pokedex", "team", "from-file"]),
+                )
+                // export/shinies
+                .arg(
+                    clap::Arg::new("shinies")
+                        .help("Put the shiny version of every Pokemon on the sheet as well")
+                        .long("shinies")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("sheet"),
+                )
+                .after_help(
+                    "Tip: Export a row of Pokemon for a wiki page!
+Example: `rustmon export svg --name \"pikachu raichu\" --output pikachu.svg`
+Example: `rustmon export png --sheet --scale 2 --output pokedex.png`",
+                ),
+        )
         // fetch subcommand
         .subcommand(
             clap::Command::new("fetch")
                 .about("Fetch the latest colorscripts from the repository")
+                // fetch/alpha_threshold
+                .arg(
+                    clap::Arg::new("alpha_threshold")
+                        .help("Leave out pixels less opaque than this, from 1 to 255. The rest are drawn fully opaque. [default: 1]")
+                        .long("alpha-threshold")
+                        .value_parser(clap::value_parser!(u8).range(1..)),
+                )
+                // fetch/blend
+                .arg(
+                    clap::Arg::new("blend")
+                        .help("Blend partly transparent pixels against this background color instead of drawing them at full color, e.g. `#1e1e2e`")
+                        .long("blend")
+                        .value_parser(|color: &str| color.parse::<crate::structs::Rgb>()),
+                )
                 // fetch/extract_destination
                 .arg(
                     clap::Arg::new("extract_destination")
-                        .help("eXtract the colorscripts archive to a custom location")
+                        .help(format!(
+                            "eXtract the colorscripts archive to a custom location [default: {}]",
+                            crate::constants::DATA_DIRECTORY.display()
+                        ))
                         .short('x')
-                        .long("extract-destination")
-                        .default_value(crate::constants::DATA_DIRECTORY.to_str().unwrap()),
+                        .long("extract-destination"),
+                )
+                // fetch/heights
+                .arg(
+                    clap::Arg::new("heights")
+                        .help("Also generate the small and big sizes fitted to these numbers of rows, for `print --height`, e.g. `8,12`")
+                        .long("heights")
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .value_parser(clap::value_parser!(u16).range(1..)),
                 )
                 // fetch/verbose
                 .arg(
@@ -31,12 +163,19 @@ pub fn argument_parser() -> clap::ArgMatches {
                 // list/forms
                 .arg(
                     clap::Arg::new("forms")
-                        .help("Print a list of forms of the specified Pokemon")
+                        .help("Print a list of forms of the specified Pokemon. Natural names like `alolan raichu` also work.")
                         .short('f')
                         .long("forms")
                         .default_value("")
                         .hide_default_value(true),
                 )
+                // list/lang
+                .arg(
+                    clap::Arg::new("lang")
+                        .help("Language of the names, e.g. `eng`, `jpn`, `jpn_ro` or `chs`. `ja` and `zh` also work.")
+                        .long("lang")
+                        .default_value("eng"),
+                )
                 .after_help(
                     "Tip: Use `grep` to search for a specific Pokemon form!
 Example: `rustmon list | grep 'pikachu'`
@@ -45,93 +184,44 @@ For more advanced usage, use `less` or `more` to scroll through the list!",
         )
         // print subcommand
         .subcommand(
-            clap::Command::new("print")
-                .about("Print a Pokemon colorscript")
-                .arg_required_else_help(true)
-                // print/big
-                .arg(
-                    clap::Arg::new("big")
-                        .help("Print a bigger version of the colorscript")
-                        .short('b')
-                        .long("big")
-                        .action(clap::ArgAction::SetTrue),
+            print_command()
+                // print/format
+                .arg(
+                    clap::Arg::new("format")
+                        .help("What to print. `html` keeps the half-block look in a `<pre>`, `svg` draws every pixel as a square.")
+                        .long("format")
+                        .default_value("ansi")
+                        .value_parser(
+                            clap::builder::PossibleValuesParser::new(["ansi", "html", "svg"])
+                                .map(|s| s.parse::<crate::structs::Format>().unwrap()),
+                        ),
+                ),
+        )
+        // say subcommand
+        .subcommand(
+            clap::Command::new("say")
+                .about("Print a speaking Pokemon")
+                .after_help(
+                    "Tip: Pipe the output to `rustmon say` to see the Pokemon speak!
+Example: `echo \"Never gonna give you up\" | rustmon say`"
                 )
-                // print/form
+                // say/form
                 .arg(
                     clap::Arg::new("form")
-                        .help("Print Pokemon by list of space-separated forms. Follows the order of the names/Pokedex number specified. If not specified, it will print the regular form. Has no effect on random Pokemon.")
+                        .help("Form of the speaking Pokemon. Natural names like `alolan raichu` also work with `--name`.")
                         .short('f')
                         .long("form")
                         .default_value("regular")
-                        .value_delimiter(' ')
-                        .requires("name_or_pokedex"),
-                    )
-                // print/hide-name
-                .arg(
-                    clap::Arg::new("hide-name")
-                        .help("Do not print Pokemon name")
-                        .long("hide-name")
-                        .action(clap::ArgAction::SetTrue),
+                        .value_delimiter(' '),
                 )
-                // print/name
+                // say/name
                 .arg(
                     clap::Arg::new("name")
-                        .help("Print Pokemon by list of space-separated names. Use `random` to print a random Pokemon.")
+                        .help("Name of the speaking Pokemon. Use `random` to pick a random Pokemon.")
                         .short('n')
                         .long("name")
-                        .default_value("")
-                        .hide_default_value(true)
-                        .value_delimiter(' ')
-                        .conflicts_with("pokedex")
-                )
-                // print/pokedex
-                .arg(
-                    clap::Arg::new("pokedex")
-                        .help("Print Pokemon by list of space-separated Pokedex numbers. Use `0` to print a random Pokemon.")
-                        .short('p')
-                        .long("pokedex")
-                        // TODO: use a dynamic range instead of 0..906
-                        // try not to hardcode?
-                        .value_parser(clap::value_parser!(u16).range(0..906))
-                        .default_value("0")
-                        .hide_default_value(true)
-                        .value_delimiter(' ')
-                        .conflicts_with("name")
-                )
-                // print/shiny
-                .arg(
-                    clap::Arg::new("shiny")
-                        .help(
-                            "Rate of printing the shiny version of the colorscript (e.g. 0.10 for 10% chance)",
-                        )
-                        .short('s')
-                        .long("shiny")
-                        .value_parser(clap::value_parser!(f32))
-                        .default_value("0.00"),
-                )
-                // print/spacing
-                .arg(
-                    clap::Arg::new("spacing")
-                        .help(
-                            "Number of spaces between colorscripts",
-                        )
-                        .long("spacing")
-                        .value_parser(clap::value_parser!(u8).range(0..21))
-                        .default_value("4"),
-                )
-                .group(
-                    clap::ArgGroup::new("name_or_pokedex")
-                        .args(["name", "pokedex"])
-                        .required(false),
-                )
-        )
-        // say subcommand
-        .subcommand(
-            clap::Command::new("say")
-                .about("Print a speaking Pokemon")
-                .after_help(
-                    "Tip: Pipe the output to `rustmon say` to see the Pokemon speak!
-Example: `echo \"Never gonna give you up\" | rustmon say`"
+                        .default_value("random")
+                        .value_delimiter(' '),
                 )
                 // say/text
                 .arg(
@@ -144,7 +234,395 @@ Example: `echo \"Never gonna give you up\" | rustmon say`"
                         .required(false)
                 )
         )
+        // team subcommand
+        .subcommand(
+            clap::Command::new("team")
+                .about("Manage teams saved in the config file")
+                .subcommand(
+                    clap::Command::new("add")
+                        .about("Add Pokemon to a team, creating the team if needed")
+                        // team/add/team
+                        .arg(
+                            clap::Arg::new("team")
+                                .help("Name of the team")
+                                .required(true),
+                        )
+                        // team/add/selectors
+                        .arg(
+                            clap::Arg::new("selectors")
+                                .help("Pokemon to add, e.g. `pikachu:shiny`, `alolan-raichu` or `charizard:mega-x`")
+                                .required(true)
+                                .num_args(1..),
+                        ),
+                )
+                .subcommand(
+                    clap::Command::new("remove")
+                        .about("Remove Pokemon from a team, or the whole team if no Pokemon are given")
+                        // team/remove/team
+                        .arg(
+                            clap::Arg::new("team")
+                                .help("Name of the team")
+                                .required(true),
+                        )
+                        // team/remove/selectors
+                        .arg(
+                            clap::Arg::new("selectors")
+                                .help("Pokemon to remove")
+                                .num_args(0..),
+                        ),
+                )
+                .subcommand(
+                    clap::Command::new("list")
+                        .about("List saved teams and aliases")
+                        // team/list/team
+                        .arg(
+                            clap::Arg::new("team")
+                                .help("Only list this team"),
+                        ),
+                )
+                .subcommand_required(true)
+                .after_help(
+                    "Tip: Print a saved team with `rustmon print --team <team>`!
+Aliases are set in the `[aliases]` table of the config file, e.g. `sparky = \"pikachu:shiny\"`"
+                ),
+        )
         .subcommand_required(true)
         // finalize
-        .get_matches();
+        .get_matches()
+}
+
+/// The `print` subcommand, `export` takes the same arguments
+fn print_command() -> clap::Command {
+    clap::Command::new("print")
+        .about("Print a Pokemon colorscript")
+        .arg_required_else_help(true)
+        // print/align
+        .arg(
+            clap::Arg::new("align")
+                .help("Position of every row within the terminal width [default: left]")
+                .long("align")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["left", "center", "right"])
+                        .map(|s| s.parse::<crate::structs::HorizontalAlignment>().unwrap()),
+                ),
+        )
+        // print/big
+        .arg(
+            clap::Arg::new("big")
+                .help("Print a bigger version of the colorscript")
+                .short('b')
+                .long("big")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/charset
+        .arg(
+            clap::Arg::new("charset")
+                .help("Characters to draw with. `ascii` works on serial consoles and log viewers that cannot show block glyphs. [default: unicode]")
+                .long("charset")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["unicode", "ascii"])
+                        .map(|s| s.parse::<crate::structs::Charset>().unwrap()),
+                ),
+        )
+        // print/columns
+        .arg(
+            clap::Arg::new("columns")
+                .help("Maximum number of colorscripts per row. If not specified, rows are fitted to the terminal width.")
+                .short('c')
+                .long("columns")
+                .value_parser(clap::value_parser!(u16).range(1..)),
+        )
+        // print/flip
+        .arg(
+            clap::Arg::new("flip")
+                .help("Mirror the colorscripts horizontally")
+                .long("flip")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/form
+        .arg(
+            clap::Arg::new("form")
+                .help("Print Pokemon by list of space-separated forms. Follows the order of the names/Pokedex number specified. If not specified, it will print the regular form. Has no effect on random Pokemon.")
+                .short('f')
+                .long("form")
+                .default_value("regular")
+                .value_delimiter(' ')
+                .requires("name_or_pokedex"),
+            )
+        // print/daltonize
+        .arg(
+            clap::Arg::new("daltonize")
+                .help("Shift the colors so they can be told apart with a kind of color blindness. Also turns on `--shiny-indicator`.")
+                .long("daltonize")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new([
+                        "protanopia",
+                        "deuteranopia",
+                        "tritanopia",
+                    ])
+                    .map(|s| s.parse::<crate::structs::ColorDeficiency>().unwrap()),
+                ),
+        )
+        // print/filter
+        .arg(
+            clap::Arg::new("filter")
+                .help("Filter for scaling down to `--height` [default: triangle]")
+                .long("filter")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new([
+                        "nearest", "triangle", "catmull-rom", "gaussian", "lanczos3",
+                    ])
+                    .map(|s| s.parse::<crate::structs::Filter>().unwrap()),
+                ),
+        )
+        // print/from-file
+        .arg(
+            clap::Arg::new("from-file")
+                .help("Read Pokemon from a file with one selector per line, or from stdin with `-`. Output of `rustmon list` also works.")
+                .long("from-file")
+                .value_name("PATH")
+                .conflicts_with_all(["name", "pokedex", "team"]),
+        )
+        // print/height
+        .arg(
+            clap::Arg::new("height")
+                .help("Scale colorscripts down to fit this number of rows, keeping their aspect ratio. Shorter colorscripts are left as they are.")
+                .long("height")
+                .value_parser(clap::value_parser!(u16).range(1..)),
+        )
+        // print/grayscale
+        .arg(
+            clap::Arg::new("grayscale")
+                .help("Print the colorscripts in shades of gray")
+                .long("grayscale")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/hide-name
+        .arg(
+            clap::Arg::new("hide-name")
+                .help("Do not print Pokemon name labels")
+                .long("hide-name")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/hue-shift
+        .arg(
+            clap::Arg::new("hue-shift")
+                .help("Rotate the hues of the colorscripts by a number of degrees, e.g. `180`")
+                .long("hue-shift")
+                .allow_negative_numbers(true)
+                .value_parser(clap::value_parser!(f32)),
+        )
+        // print/invert
+        .arg(
+            clap::Arg::new("invert")
+                .help("Invert the colors of the colorscripts")
+                .long("invert")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/label
+        .arg(
+            clap::Arg::new("label")
+                .help("Template for the label of every colorscript. Placeholders: {name}, {slug}, {dex}, {form}, {shiny}. Use {shiny:text} to show text only for shiny colorscripts. [default: \"{name} {form} {shiny:✨}\"]")
+                .short('l')
+                .long("label"),
+        )
+        // print/label-color
+        .arg(
+            clap::Arg::new("label-color")
+                .help("Color every label with the dominant color of its colorscript")
+                .long("label-color")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/label-position
+        .arg(
+            clap::Arg::new("label-position")
+                .help("Print labels above or below the colorscripts [default: above]")
+                .long("label-position")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["above", "below"])
+                        .map(|s| s.parse::<crate::structs::LabelPosition>().unwrap()),
+                ),
+        )
+        // print/lang
+        .arg(
+            clap::Arg::new("lang")
+                .help("Language of the names in labels, e.g. `eng`, `jpn`, `jpn_ro` or `chs`. `ja` and `zh` also work. Names in any language are accepted as input regardless. [default: eng]")
+                .long("lang"),
+        )
+        // print/light-bg
+        .arg(
+            clap::Arg::new("light-bg")
+                .help("Darken colors that are hard to see on a light terminal background. The background is asked from the terminal, or read from `background` in the config file.")
+                .long("light-bg")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/margin
+        .arg(
+            clap::Arg::new("margin")
+                .help("Number of blank lines above and below the output, and spaces on its sides [default: 0]")
+                .long("margin")
+                .value_parser(clap::value_parser!(u8).range(0..21)),
+        )
+        // print/name
+        .arg(
+            clap::Arg::new("name")
+                .help("Print Pokemon by list of space-separated names. Use `random` to print a random Pokemon. Natural names like `alolan raichu`, `mega charizard x` or `gmax pikachu` select the form too. Append `:shiny` to force a shiny, e.g. `pikachu:shiny`. Aliases from the config file also work.")
+                .short('n')
+                .long("name")
+                .default_value("")
+                .hide_default_value(true)
+                .value_delimiter(' ')
+                .conflicts_with("pokedex")
+        )
+        // print/outline
+        .arg(
+            clap::Arg::new("outline")
+                .help("Draw a line in this color around the colorscripts, e.g. `#ffffff`")
+                .long("outline")
+                .value_parser(|color: &str| color.parse::<crate::structs::Rgb>()),
+        )
+        // print/padding
+        .arg(
+            clap::Arg::new("padding")
+                .help("Number of spaces on both sides of every colorscript [default: 0]")
+                .long("padding")
+                .value_parser(clap::value_parser!(u8).range(0..21)),
+        )
+        // print/per-line
+        .arg(
+            clap::Arg::new("per-line")
+                .help("Print every line from `--from-file` as its own block instead of laying them all out in a row")
+                .long("per-line")
+                .action(clap::ArgAction::SetTrue)
+                .requires("from-file"),
+        )
+        // print/pokedex
+        .arg(
+            clap::Arg::new("pokedex")
+                .help("Print Pokemon by list of space-separated Pokedex numbers. Use `0` to print a random Pokemon.")
+                .short('p')
+                .long("pokedex")
+                // TODO: use a dynamic range instead of 0..906
+                // try not to hardcode?
+                .value_parser(clap::value_parser!(u16).range(0..906))
+                .default_value("0")
+                .hide_default_value(true)
+                .value_delimiter(' ')
+                .conflicts_with("name")
+        )
+        // print/protocol
+        .arg(
+            clap::Arg::new("protocol")
+                .help("Draw the colorscripts as images with a terminal graphics protocol. `auto` picks the best one the terminal supports, and falls back to blocks. [default: blocks]")
+                .long("protocol")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new([
+                        "blocks", "sixel", "kitty", "iterm", "auto",
+                    ])
+                    .map(|s| s.parse::<crate::structs::Protocol>().unwrap()),
+                ),
+        )
+        // print/ramp
+        .arg(
+            clap::Arg::new("ramp")
+                .help("Characters from dark to bright for `--charset ascii`, the first one is used for transparent pixels [default: \" .:-=+*#%@\"]")
+                .long("ramp")
+                .value_parser(|ramp: &str| {
+                    if ramp.chars().count() < 2 {
+                        Err("The ramp needs at least two characters".to_string())
+                    } else {
+                        Ok(ramp.to_string())
+                    }
+                }),
+        )
+        // print/scale
+        .arg(
+            clap::Arg::new("scale")
+                .help("Scale the pixels of colorscripts up by a whole number, after fitting them to `--height` [default: 1]")
+                .long("scale")
+                .value_parser(clap::value_parser!(u8).range(1..9)),
+        )
+        // print/shadow
+        .arg(
+            clap::Arg::new("shadow")
+                .help("Cast a drop shadow down and to the right of the colorscripts")
+                .long("shadow")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/shiny
+        .arg(
+            clap::Arg::new("shiny")
+                .help(
+                    "Rate of printing the shiny version of the colorscript (e.g. 0.10 for 10% chance) [default: 0.00]",
+                )
+                .short('s')
+                .long("shiny")
+                .value_parser(clap::value_parser!(f32)),
+        )
+        // print/shiny-indicator
+        .arg(
+            clap::Arg::new("shiny-indicator")
+                .help("Add `[shiny]` to the labels of shiny colorscripts, even with `--hide-name`, so shininess does not depend on colors alone")
+                .long("shiny-indicator")
+                .action(clap::ArgAction::SetTrue),
+        )
+        // print/silhouette
+        .arg(
+            clap::Arg::new("silhouette")
+                .help("Paint every pixel of the colorscripts in one color, black if no color is given")
+                .long("silhouette")
+                .num_args(0..=1)
+                .default_missing_value(crate::constants::DEFAULT_SILHOUETTE_COLOR)
+                .value_parser(|color: &str| color.parse::<crate::structs::Rgb>()),
+        )
+        // print/size
+        .arg(
+            clap::Arg::new("size")
+                .help("Size of the colorscripts. `tiny` packs 2x2 pixels into a cell, `sextant` 2x3 and `micro` 2x4 with braille dots. `auto` picks the largest size that fits the terminal. [default: small]")
+                .long("size")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new([
+                        "small", "big", "tiny", "sextant", "micro", "quadrant", "braille",
+                        "auto",
+                    ])
+                    .map(|s| s.parse::<crate::structs::Size>().unwrap()),
+                )
+                .conflicts_with("big"),
+        )
+        // print/spacing
+        .arg(
+            clap::Arg::new("spacing")
+                .help(
+                    "Number of spaces between colorscripts [default: 4]",
+                )
+                .long("spacing")
+                .value_parser(clap::value_parser!(u8).range(0..21)),
+        )
+        // print/team
+        .arg(
+            clap::Arg::new("team")
+                .help("Print a team saved in the config file. Use `rustmon team` to manage teams.")
+                .short('t')
+                .long("team")
+                .conflicts_with_all(["name", "pokedex"])
+        )
+        // print/valign
+        .arg(
+            clap::Arg::new("valign")
+                .help("Vertical position of shorter colorscripts in a row. `bottom` puts them on a shared ground line. [default: top]")
+                .long("valign")
+                .value_parser(
+                    clap::builder::PossibleValuesParser::new(["top", "center", "bottom"])
+                        .map(|s| s.parse::<crate::structs::VerticalAlignment>().unwrap()),
+                ),
+        )
+        .after_help(
+            "Tip: Pipe Pokemon into `rustmon print --from-file -` to print them!
+Example: `rustmon list | grep 'chu' | rustmon print --from-file -`"
+        )
+        .group(
+            clap::ArgGroup::new("name_or_pokedex")
+                .args(["name", "pokedex", "team", "from-file"])
+                .required(false),
+        )
 }
@@ -0,0 +1,371 @@
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+
+/// Decide whether to write color escape codes
+/// in auto mode, `NO_COLOR` turns colors off, `CLICOLOR_FORCE` turns them on, otherwise only a terminal that is not `TERM=dumb` gets colors
+/// see https://no-color.org and https://bixense.com/clicolors
+pub fn enabled(mode: crate::structs::ColorMode) -> bool {
+    match mode {
+        crate::structs::ColorMode::Always => true,
+        crate::structs::ColorMode::Never => false,
+        crate::structs::ColorMode::Auto => {
+            if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+                false
+            } else if std::env::var_os("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                true
+            } else if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+                // dumb terminals cannot handle escape codes
+                false
+            } else {
+                std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Resolve the global color flags of a subcommand
+pub fn resolve_options(args: &clap::ArgMatches) -> crate::structs::ColorOptions {
+    let depth = match args.get_one::<crate::structs::ColorDepth>("color-depth") {
+        Some(crate::structs::ColorDepth::Auto) | None => detect_depth(),
+        Some(depth) => *depth,
+    };
+
+    crate::structs::ColorOptions {
+        enabled: enabled(
+            args.get_one::<crate::structs::ColorMode>("color")
+                .copied()
+                .unwrap_or_default(),
+        ),
+        depth,
+        dither: args.get_flag("dither"),
+    }
+}
+
+/// Guess the color depth of the terminal from `COLORTERM` and `TERM`
+/// terminals that do not say anything are assumed to support truecolor
+pub fn detect_depth() -> crate::structs::ColorDepth {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return crate::structs::ColorDepth::TrueColor;
+    }
+
+    match std::env::var("TERM") {
+        Ok(term)
+            if term.contains("truecolor") || term.contains("24bit") || term.contains("direct") =>
+        {
+            crate::structs::ColorDepth::TrueColor
+        }
+        Ok(term) if term.contains("256") => crate::structs::ColorDepth::Ansi256,
+        Ok(term) if term == "linux" || term.starts_with("vt") || term == "ansi" => {
+            crate::structs::ColorDepth::Ansi16
+        }
+        // most other terminals, e.g. `xterm` or `screen`, can do 256 colors
+        Ok(term) if !term.is_empty() => crate::structs::ColorDepth::Ansi256,
+        _ => crate::structs::ColorDepth::TrueColor,
+    }
+}
+
+/// Ask the terminal for its background color with OSC 11
+/// gives up if the output is not a terminal, or if the terminal does not answer within a moment
+pub fn query_background() -> Option<crate::structs::Rgb> {
+    // answers end with BEL or ST, like the query
+    let response = query_terminal(b"\x1b]11;?\x07", |response| {
+        response.ends_with(b"\x07") || response.ends_with(b"\x1b\\")
+    })?;
+
+    parse_background_response(&String::from_utf8_lossy(&response))
+}
+
+/// Write a query to the terminal and read its answer, up to where `is_complete` says it ends
+/// gives up if the output is not a terminal, or if the terminal does not answer within a moment
+pub fn query_terminal(query: &[u8], is_complete: fn(&[u8]) -> bool) -> Option<Vec<u8>> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+
+    // the answer would be echoed and wait for a newline otherwise
+    crossterm::terminal::enable_raw_mode().ok()?;
+
+    let response = tty
+        .write_all(query)
+        .and_then(|_| tty.flush())
+        .ok()
+        .and_then(|_| {
+            // terminals that do not know the query never answer, so every read waits only until the deadline
+            // nothing is left reading from the terminal afterwards, which would take the next keystroke
+            let deadline = std::time::Instant::now()
+                + std::time::Duration::from_millis(crate::constants::TERMINAL_QUERY_TIMEOUT);
+            let mut response: Vec<u8> = Vec::new();
+            let mut byte = [0u8];
+            while !is_complete(&response) {
+                let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+                if !wait_for_input(&tty, remaining) || tty.read(&mut byte).ok()? != 1 {
+                    return None;
+                }
+                response.push(byte[0]);
+            }
+            Some(response)
+        });
+
+    let _ = crossterm::terminal::disable_raw_mode();
+
+    response
+}
+
+/// Wait until the terminal has something to read, giving up after the timeout
+#[cfg(unix)]
+fn wait_for_input(tty: &std::fs::File, timeout: std::time::Duration) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    let mut poll_fd = libc::pollfd {
+        fd: tty.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: the file descriptor stays open and `poll_fd` outlives the call
+    let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) };
+
+    ready > 0 && poll_fd.revents & libc::POLLIN != 0
+}
+
+#[cfg(not(unix))]
+fn wait_for_input(_tty: &std::fs::File, _timeout: std::time::Duration) -> bool {
+    false
+}
+
+/// Parse the answer to an OSC 11 query, e.g. `\x1b]11;rgb:ffff/ffff/ffff\x07`
+/// every channel has one to four hex digits
+fn parse_background_response(response: &str) -> Option<crate::structs::Rgb> {
+    let color = response.split("rgb:").nth(1)?;
+    let color = color.trim_end_matches(['\x07', '\x1b', '\\']);
+
+    let mut channels = color.split('/').map(|channel| {
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        let max = 16u32.checked_pow(channel.len() as u32)?.checked_sub(1)?;
+        (max > 0 && channel.len() <= 4).then(|| (value * 255 / max) as u8)
+    });
+
+    Some(crate::structs::Rgb(
+        channels.next()??,
+        channels.next()??,
+        channels.next()??,
+    ))
+}
+
+/// Escape code for a foreground or background color at a color depth
+pub fn escape_code(
+    color: (u8, u8, u8),
+    background: bool,
+    depth: crate::structs::ColorDepth,
+) -> String {
+    let (r, g, b) = color;
+    match depth {
+        crate::structs::ColorDepth::Auto | crate::structs::ColorDepth::TrueColor => {
+            format!(
+                "\x1b[{};2;{};{};{}m",
+                if background { 48 } else { 38 },
+                r,
+                g,
+                b
+            )
+        }
+        crate::structs::ColorDepth::Ansi256 => {
+            format!(
+                "\x1b[{};5;{}m",
+                if background { 48 } else { 38 },
+                nearest_256(color)
+            )
+        }
+        crate::structs::ColorDepth::Ansi16 => {
+            let index = nearest_16(color);
+            // 30-37 and 90-97 for foregrounds, 40-47 and 100-107 for backgrounds
+            let base = match (background, index < 8) {
+                (false, true) => 30,
+                (false, false) => 90 - 8,
+                (true, true) => 40,
+                (true, false) => 100 - 8,
+            };
+            format!("\x1b[{}m", base + index)
+        }
+    }
+}
+
+/// Rewrite the colors of a colorscript line for the terminal
+/// `row` is the line number, used together with the column for the dithering pattern
+pub fn render_line(
+    line: &str,
+    row: usize,
+    size: crate::structs::Size,
+    colors: &crate::structs::ColorOptions,
+) -> String {
+    if !colors.enabled {
+        return strip(line);
+    }
+    if colors.depth == crate::structs::ColorDepth::TrueColor {
+        return line.to_string();
+    }
+
+    let mut rendered_line = String::new();
+    // reduced escape codes last written for the foreground and the background
+    // colors carry over to the next glyphs, so they are reduced again for every glyph when dithering
+    let mut codes_written: [Option<String>; 2] = [None, None];
+
+    for (column, cell) in crate::colorscript::parse_cells(line).iter().enumerate() {
+        let colors_set = [cell.foreground, cell.background];
+
+        // a color can only be taken away with a reset
+        if (0..2).any(|layer| codes_written[layer].is_some() && colors_set[layer].is_none()) {
+            rendered_line.push_str("\x1b[0m");
+            codes_written = [None, None];
+        }
+
+        for (layer, color) in colors_set.iter().enumerate() {
+            let Some(color) = *color else { continue };
+            let background = layer == 1;
+
+            // the pixel of the cell the color stands for, so the pattern follows the pixels of every size
+            // the foreground is taken from the top left of the cell and the background from the bottom right
+            let (cell_width, cell_height) = size.cell_pixels();
+            let x = column / size.cell_glyphs() * cell_width + layer * (cell_width - 1);
+            let y = row * cell_height + layer * (cell_height - 1);
+            let color = if colors.dither {
+                dither(color, x, y, colors.depth)
+            } else {
+                color
+            };
+
+            // only write the codes that changed
+            let code = escape_code(color, background, colors.depth);
+            if codes_written[layer].as_ref() != Some(&code) {
+                rendered_line.push_str(&code);
+                codes_written[layer] = Some(code);
+            }
+        }
+
+        rendered_line.push(cell.glyph);
+    }
+
+    // reset at the end of the line, like the colorscript does
+    if codes_written.iter().any(|code| code.is_some()) {
+        rendered_line.push_str("\x1b[0m");
+    }
+
+    rendered_line
+}
+
+/// Remove the escape codes from a colorscript line, leaving only the glyphs
+/// glyphs with a background color cover the whole cell, so they become full blocks
+pub fn strip(line: &str) -> String {
+    crate::colorscript::parse_cells(line)
+        .iter()
+        .map(|cell| {
+            if cell.glyph != ' ' && cell.background.is_some() {
+                '█'
+            } else {
+                cell.glyph
+            }
+        })
+        .collect()
+}
+
+// levels of the 6x6x6 color cube in the xterm 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// the basic 16 colors as xterm shows them
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+// 4x4 bayer matrix for ordered dithering
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Index of the closest color in the xterm 256 color palette, from the color cube or the gray ramp
+fn nearest_256(color: (u8, u8, u8)) -> u8 {
+    let cube_index = |value: u8| -> usize {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| (**level as i32 - value as i32).abs())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (
+        cube_index(color.0),
+        cube_index(color.1),
+        cube_index(color.2),
+    );
+    let cube_color = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    // gray ramp from 8 to 238 in steps of 10
+    let average = (color.0 as u32 + color.1 as u32 + color.2 as u32) / 3;
+    let gray_index = ((average.saturating_sub(3)) / 10).min(23) as u8;
+    let gray_level = 8 + 10 * gray_index;
+
+    if distance(color, (gray_level, gray_level, gray_level)) < distance(color, cube_color) {
+        232 + gray_index
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
+
+/// Index of the closest of the basic 16 colors
+fn nearest_16(color: (u8, u8, u8)) -> u8 {
+    ANSI_16
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, palette_color)| distance(color, **palette_color))
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
+
+/// Nudge a color by the bayer threshold of its position, so that reducing it mixes the nearby palette colors
+fn dither(
+    color: (u8, u8, u8),
+    x: usize,
+    y: usize,
+    depth: crate::structs::ColorDepth,
+) -> (u8, u8, u8) {
+    // roughly the gap between palette colors
+    let spread = match depth {
+        crate::structs::ColorDepth::Ansi16 => 64.0,
+        _ => 40.0,
+    };
+    let threshold = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16.0 - 0.5;
+    let nudge = |value: u8| {
+        (value as f32 + threshold * spread)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+
+    (nudge(color.0), nudge(color.1), nudge(color.2))
+}
+
+/// Squared euclidean distance between two colors
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
@@ -0,0 +1,663 @@
+// reading and writing the colorscript formats made by `fetch`
+// small colorscripts stack two pixels in a cell with `▀` and `▄`, using the foreground and background colors
+// quadrant, sextant and braille colorscripts pack 2x2, 2x3 and 2x4 pixels into a cell the same way
+// big colorscripts use `██` in the foreground color for every pixel
+// transparent pixels are spaces, or left out of the glyph
+
+/// Parse a colorscript into a pixel grid
+/// pixels covered by the glyph of a cell get the foreground color, the rest get the background color
+pub fn parse(colorscript: &str, size: crate::structs::Size) -> crate::structs::PixelGrid {
+    // every line as a list of cells, each cell being the glyph and its colors
+    let rows: Vec<Vec<Cell>> = colorscript.lines().map(parse_cells).collect();
+
+    if size == crate::structs::Size::Big {
+        return parse_big(&rows);
+    }
+
+    let (cell_width, cell_height) = size.cell_pixels();
+    let width = rows.iter().map(|cells| cells.len()).max().unwrap_or(0) * cell_width;
+    let height = rows.len() * cell_height;
+
+    let mut grid = crate::structs::PixelGrid::new(width, height);
+
+    for (row, cells) in rows.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            // unknown glyphs are left transparent
+            let Some(mask) = glyph_mask(cell.glyph, size) else {
+                continue;
+            };
+
+            for i in 0..cell_width * cell_height {
+                let color = if mask & (1 << i) != 0 {
+                    cell.foreground
+                } else {
+                    cell.background
+                };
+                if let Some(color) = color {
+                    grid.set(
+                        column * cell_width + i % cell_width,
+                        row * cell_height + i / cell_width,
+                        opaque(color),
+                    );
+                }
+            }
+        }
+    }
+
+    grid
+}
+
+/// Write a pixel grid as a colorscript
+/// colors are only written when they change, and every line ends with a reset
+/// pixels with any transparency left are drawn, only fully transparent ones are left out
+pub fn write(grid: &crate::structs::PixelGrid, size: crate::structs::Size) -> String {
+    match size {
+        crate::structs::Size::Big => write_big(grid),
+        crate::structs::Size::Small | crate::structs::Size::Auto => write_cells(&small_cells(grid)),
+        _ => write_cells(&packed_cells(grid, size)),
+    }
+}
+
+/// Write a pixel grid with ascii characters, picked from the ramp by brightness
+/// a cell covers the same pixels as the glyphs of the size, and gets the average color of them
+/// partly transparent cells are drawn darker, fully transparent ones get the first character of the ramp
+pub fn write_ascii(
+    grid: &crate::structs::PixelGrid,
+    size: crate::structs::Size,
+    ramp: &str,
+) -> String {
+    let ramp: Vec<char> = ramp.chars().collect();
+    // big is drawn with two characters for every pixel like its glyphs
+    let (cell_width, cell_height) = size.cell_pixels();
+    let repeat = size.cell_glyphs();
+
+    let mut rows: Vec<Vec<OutputCell>> = Vec::new();
+
+    for y in (0..grid.height).step_by(cell_height) {
+        let mut cells: Vec<OutputCell> = Vec::new();
+
+        for x in (0..grid.width).step_by(cell_width) {
+            let pixels: Vec<[u8; 4]> = (0..cell_width * cell_height)
+                .map(|i| grid.get(x + i % cell_width, y + i / cell_width))
+                .collect();
+            let opaque_pixels: Vec<&[u8; 4]> =
+                pixels.iter().filter(|pixel| pixel[3] != 0).collect();
+
+            let cell = if opaque_pixels.is_empty() || ramp.len() < 2 {
+                (ramp.first().copied().unwrap_or(' '), None, None)
+            } else {
+                let color = average(opaque_pixels.iter().copied());
+                let coverage = opaque_pixels.len() as f32 / pixels.len() as f32;
+                let brightness = luminance(color) * coverage;
+
+                // the first character is for transparent pixels only, so even black pixels get a visible one
+                let index = 1 + (brightness * (ramp.len() - 2) as f32).round() as usize;
+                (ramp[index.min(ramp.len() - 1)], Some(color), None)
+            };
+
+            for _ in 0..repeat {
+                cells.push(cell);
+            }
+        }
+
+        rows.push(cells);
+    }
+
+    write_cells(&rows)
+}
+
+/// Turn an image into a pixel grid
+pub fn from_image(img: &image::DynamicImage) -> crate::structs::PixelGrid {
+    let img = img.to_rgba8();
+    let mut grid = crate::structs::PixelGrid::new(img.width() as usize, img.height() as usize);
+
+    for (x, y, pixel) in img.enumerate_pixels() {
+        grid.set(x as usize, y as usize, pixel.0);
+    }
+
+    grid
+}
+
+/// Turn a pixel grid into an image
+pub fn to_image(grid: &crate::structs::PixelGrid) -> image::RgbaImage {
+    let mut img = image::RgbaImage::new(grid.width as u32, grid.height as u32);
+
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        *pixel = image::Rgba(grid.get(x as usize, y as usize));
+    }
+
+    img
+}
+
+/// Encode a pixel grid as a PNG file
+pub fn to_png(grid: &crate::structs::PixelGrid) -> image::ImageResult<Vec<u8>> {
+    let mut png: Vec<u8> = Vec::new();
+    to_image(grid).write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+    Ok(png)
+}
+
+/// Make every pixel fully opaque or fully transparent
+/// pixels below the threshold are left out, the rest are blended against the background if there is one
+pub fn apply_alpha(
+    grid: &crate::structs::PixelGrid,
+    alpha: &crate::structs::AlphaOptions,
+) -> crate::structs::PixelGrid {
+    let mut opaque_grid = crate::structs::PixelGrid::new(grid.width, grid.height);
+
+    for (i, pixel) in grid.pixels.iter().enumerate() {
+        if pixel[3] == 0 || pixel[3] < alpha.threshold {
+            continue;
+        }
+
+        let opaque_pixel = match alpha.blend {
+            Some(crate::structs::Rgb(r, g, b)) => {
+                let a = pixel[3] as f32 / 255.0;
+                let mix = |value: u8, background: u8| {
+                    (value as f32 * a + background as f32 * (1.0 - a)).round() as u8
+                };
+                [mix(pixel[0], r), mix(pixel[1], g), mix(pixel[2], b), 255]
+            }
+            None => [pixel[0], pixel[1], pixel[2], 255],
+        };
+        opaque_grid.pixels[i] = opaque_pixel;
+    }
+
+    opaque_grid
+}
+
+/// A glyph to write, with the colors it needs
+/// no foreground means any foreground will do
+type OutputCell = (char, Option<[u8; 4]>, Option<[u8; 4]>);
+
+fn small_cells(grid: &crate::structs::PixelGrid) -> Vec<Vec<OutputCell>> {
+    let mut rows: Vec<Vec<OutputCell>> = Vec::new();
+
+    for y in (0..grid.height).step_by(2) {
+        let mut cells: Vec<OutputCell> = Vec::new();
+
+        for x in 0..grid.width {
+            let upper_pixel = grid.get(x, y);
+            let lower_pixel = if y + 1 < grid.height {
+                grid.get(x, y + 1)
+            } else {
+                // fallback to upper pixel if there's no lower pixel
+                upper_pixel
+            };
+
+            cells.push(if upper_pixel[3] == 0 && lower_pixel[3] == 0 {
+                (' ', None, None)
+            } else if upper_pixel[3] == 0 {
+                ('▄', Some(lower_pixel), None)
+            } else if lower_pixel[3] == 0 {
+                ('▀', Some(upper_pixel), None)
+            } else {
+                ('▀', Some(upper_pixel), Some(lower_pixel))
+            });
+        }
+
+        rows.push(cells);
+    }
+
+    rows
+}
+
+/// Pack several pixels into every cell
+/// a cell can only show two colors, so fully covered cells are split into the two most different groups of colors
+/// cells with transparent pixels only get a foreground, as the background has to stay transparent
+fn packed_cells(
+    grid: &crate::structs::PixelGrid,
+    size: crate::structs::Size,
+) -> Vec<Vec<OutputCell>> {
+    let (cell_width, cell_height) = size.cell_pixels();
+    let mut rows: Vec<Vec<OutputCell>> = Vec::new();
+
+    for y in (0..grid.height).step_by(cell_height) {
+        let mut cells: Vec<OutputCell> = Vec::new();
+
+        for x in (0..grid.width).step_by(cell_width) {
+            // pixels of the cell, row by row
+            let pixels: Vec<[u8; 4]> = (0..cell_width * cell_height)
+                .map(|i| grid.get(x + i % cell_width, y + i / cell_width))
+                .collect();
+            let opaque_mask = mask_where(&pixels, |pixel| pixel[3] != 0);
+
+            let cell = if opaque_mask == 0 {
+                (' ', None, None)
+            } else if opaque_mask != full_mask(pixels.len())
+                || size == crate::structs::Size::Braille
+            {
+                // braille dots look wrong on a background, so they only ever get a foreground
+                let color = average(pixels.iter().filter(|pixel| pixel[3] != 0));
+                (mask_glyph(opaque_mask, size), Some(color), None)
+            } else {
+                split_colors(&pixels, size)
+            };
+
+            cells.push(cell);
+        }
+
+        rows.push(cells);
+    }
+
+    rows
+}
+
+/// Split a fully covered cell into its two most different colors
+fn split_colors(pixels: &[[u8; 4]], size: crate::structs::Size) -> OutputCell {
+    // the two pixels furthest apart are the seeds of the groups
+    let mut seeds = (pixels[0], pixels[0]);
+    let mut furthest = 0;
+    for a in pixels {
+        for b in pixels {
+            if distance(*a, *b) > furthest {
+                furthest = distance(*a, *b);
+                seeds = (*a, *b);
+            }
+        }
+    }
+
+    // every pixel goes with the seed it is closest to
+    let foreground_mask = mask_where(pixels, |pixel| {
+        distance(*pixel, seeds.0) <= distance(*pixel, seeds.1)
+    });
+    let foreground = average(
+        pixels
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| foreground_mask & (1 << i) != 0)
+            .map(|(_, pixel)| pixel),
+    );
+
+    if foreground_mask == full_mask(pixels.len()) {
+        return ('█', Some(foreground), None);
+    }
+
+    let background = average(
+        pixels
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| foreground_mask & (1 << i) == 0)
+            .map(|(_, pixel)| pixel),
+    );
+
+    (
+        mask_glyph(foreground_mask, size),
+        Some(foreground),
+        Some(background),
+    )
+}
+
+/// Write rows of cells, only writing the colors that change
+fn write_cells(rows: &[Vec<OutputCell>]) -> String {
+    let mut colorscript = String::new();
+
+    for cells in rows {
+        // colors currently set, every line starts and ends without any
+        let mut foreground: Option<[u8; 4]> = None;
+        let mut background: Option<[u8; 4]> = None;
+
+        for (glyph, wanted_foreground, wanted_background) in cells {
+            // a background can only be taken away with a reset
+            if background.is_some() && wanted_background.is_none() {
+                colorscript.push_str("\x1b[0m");
+                foreground = None;
+                background = None;
+            }
+
+            // only write the colors that changed
+            if let Some(pixel) = *wanted_foreground {
+                if foreground.map(rgb) != Some(rgb(pixel)) {
+                    colorscript.push_str(&escape_code(pixel, false));
+                    foreground = Some(pixel);
+                }
+            }
+            if let Some(pixel) = *wanted_background {
+                if background.map(rgb) != Some(rgb(pixel)) {
+                    colorscript.push_str(&escape_code(pixel, true));
+                    background = Some(pixel);
+                }
+            }
+
+            colorscript.push(*glyph);
+        }
+
+        // reset at the end of the line so that lines can be printed next to each other
+        if foreground.is_some() || background.is_some() {
+            colorscript.push_str("\x1b[0m");
+        }
+        colorscript.push('\n');
+    }
+
+    colorscript
+}
+
+fn write_big(grid: &crate::structs::PixelGrid) -> String {
+    let mut colorscript = String::new();
+
+    for y in 0..grid.height {
+        // spaces do not show the foreground color, so it only changes for the next pixel
+        let mut foreground: Option<[u8; 4]> = None;
+
+        for x in 0..grid.width {
+            let pixel = grid.get(x, y);
+
+            if pixel[3] == 0 {
+                colorscript.push_str("  ");
+            } else {
+                if foreground.map(rgb) != Some(rgb(pixel)) {
+                    colorscript.push_str(&escape_code(pixel, false));
+                    foreground = Some(pixel);
+                }
+                colorscript.push_str("██");
+            }
+        }
+
+        if foreground.is_some() {
+            colorscript.push_str("\x1b[0m");
+        }
+        colorscript.push('\n');
+    }
+
+    colorscript
+}
+
+fn parse_big(rows: &[Vec<Cell>]) -> crate::structs::PixelGrid {
+    let width = rows
+        .iter()
+        .map(|cells| cells.len().div_ceil(2))
+        .max()
+        .unwrap_or(0);
+    let mut grid = crate::structs::PixelGrid::new(width, rows.len());
+
+    for (y, cells) in rows.iter().enumerate() {
+        // only the first glyph of a pixel counts, the second one is the same
+        for (x, cell) in cells.iter().step_by(2).enumerate() {
+            if let ('█', Some(foreground)) = (cell.glyph, cell.foreground) {
+                grid.set(x, y, opaque(foreground));
+            }
+        }
+    }
+
+    grid
+}
+
+// quadrant blocks for every combination of top left, top right, bottom left and bottom right
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+// braille dot bits for every pixel, row by row
+const BRAILLE_DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+/// Glyph covering the pixels in a mask, bits go row by row from the top left
+fn mask_glyph(mask: u8, size: crate::structs::Size) -> char {
+    match size {
+        crate::structs::Size::Big | crate::structs::Size::Small | crate::structs::Size::Auto => {
+            [' ', '▀', '▄', '█'][mask as usize & 3]
+        }
+        crate::structs::Size::Quadrant => QUADRANTS[mask as usize & 15],
+        crate::structs::Size::Sextant => match mask & 63 {
+            0 => ' ',
+            // the sextants leave out the ones that already exist as half blocks
+            21 => '▌',
+            42 => '▐',
+            63 => '█',
+            mask => {
+                let offset = mask as u32 - 1 - (mask > 21) as u32 - (mask > 42) as u32;
+                char::from_u32(0x1FB00 + offset).unwrap_or('█')
+            }
+        },
+        crate::structs::Size::Braille => {
+            let dots = (0..8)
+                .filter(|i| mask & (1 << i) != 0)
+                .fold(0, |dots, i| dots | BRAILLE_DOTS[i]);
+            char::from_u32(0x2800 + dots as u32).unwrap_or(' ')
+        }
+    }
+}
+
+/// Mask of the pixels a glyph covers, or `None` if the glyph is not used by the size
+fn glyph_mask(glyph: char, size: crate::structs::Size) -> Option<u8> {
+    let (cell_width, cell_height) = size.cell_pixels();
+    (0..=full_mask(cell_width * cell_height)).find(|mask| mask_glyph(*mask, size) == glyph)
+}
+
+/// Mask with a bit for every pixel that passes the check
+fn mask_where(pixels: &[[u8; 4]], check: impl Fn(&[u8; 4]) -> bool) -> u8 {
+    pixels
+        .iter()
+        .enumerate()
+        .filter(|(_, pixel)| check(pixel))
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+fn full_mask(pixels: usize) -> u8 {
+    ((1u16 << pixels) - 1) as u8
+}
+
+/// Average color of some pixels, fully opaque
+fn average<'a>(pixels: impl Iterator<Item = &'a [u8; 4]>) -> [u8; 4] {
+    let mut sums = [0u32; 3];
+    let mut count = 0;
+    for pixel in pixels {
+        for channel in 0..3 {
+            sums[channel] += pixel[channel] as u32;
+        }
+        count += 1;
+    }
+    let count = count.max(1);
+
+    [
+        (sums[0] / count) as u8,
+        (sums[1] / count) as u8,
+        (sums[2] / count) as u8,
+        255,
+    ]
+}
+
+/// Perceived brightness of a pixel, from 0 to 1
+fn luminance(pixel: [u8; 4]) -> f32 {
+    (0.2126 * pixel[0] as f32 + 0.7152 * pixel[1] as f32 + 0.0722 * pixel[2] as f32) / 255.0
+}
+
+/// Squared distance between the colors of two pixels
+fn distance(a: [u8; 4], b: [u8; 4]) -> u32 {
+    (0..3)
+        .map(|channel| {
+            let difference = a[channel] as i32 - b[channel] as i32;
+            (difference * difference) as u32
+        })
+        .sum()
+}
+
+/// Color of a pixel without the alpha, which is not written
+fn rgb(pixel: [u8; 4]) -> [u8; 3] {
+    [pixel[0], pixel[1], pixel[2]]
+}
+
+fn escape_code(pixel: [u8; 4], background: bool) -> String {
+    let color = crossterm::style::Color::Rgb {
+        r: pixel[0],
+        g: pixel[1],
+        b: pixel[2],
+    };
+
+    if background {
+        format!("{}", crossterm::style::SetBackgroundColor(color))
+    } else {
+        format!("{}", crossterm::style::SetForegroundColor(color))
+    }
+}
+
+/// A glyph with the colors it was drawn in
+pub struct Cell {
+    pub glyph: char,
+    pub foreground: Option<(u8, u8, u8)>,
+    pub background: Option<(u8, u8, u8)>,
+}
+
+/// Split a line into cells, keeping track of the colors set by the escape codes
+/// used for everything that reads printed lines, so escape codes are only walked here
+pub fn parse_cells(line: &str) -> Vec<Cell> {
+    let mut cells: Vec<Cell> = Vec::new();
+    let mut chars = line.chars();
+    let mut foreground: Option<(u8, u8, u8)> = None;
+    let mut background: Option<(u8, u8, u8)> = None;
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            cells.push(Cell {
+                glyph: c,
+                foreground,
+                background,
+            });
+            continue;
+        }
+
+        // read the escape code up to the final `m`, e.g. `[38;2;255;0;0m`
+        let mut code = String::new();
+        for c in chars.by_ref() {
+            if c == 'm' {
+                break;
+            }
+            code.push(c);
+        }
+
+        let parameters: Vec<&str> = code.trim_start_matches('[').split(';').collect();
+        match parameters.as_slice() {
+            [""] | ["0"] => {
+                foreground = None;
+                background = None;
+            }
+            ["39"] => foreground = None,
+            ["49"] => background = None,
+            ["38", "2", r, g, b] => foreground = parse_rgb(r, g, b),
+            ["48", "2", r, g, b] => background = parse_rgb(r, g, b),
+            // anything else does not change the pixels
+            _ => {}
+        }
+    }
+
+    cells
+}
+
+fn parse_rgb(r: &str, g: &str, b: &str) -> Option<(u8, u8, u8)> {
+    Some((r.parse().ok()?, g.parse().ok()?, b.parse().ok()?))
+}
+
+fn opaque(color: (u8, u8, u8)) -> [u8; 4] {
+    [color.0, color.1, color.2, 255]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::{PixelGrid, Size};
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    fn grid(width: usize, height: usize, pixels: &[[u8; 4]]) -> PixelGrid {
+        PixelGrid {
+            width,
+            height,
+            pixels: pixels.to_vec(),
+        }
+    }
+
+    // red on the left and blue on the right, with holes, so every cell of every size has at most one color
+    fn striped_grid() -> PixelGrid {
+        let mut grid = PixelGrid::new(4, 12);
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                if (x + y) % 3 != 0 {
+                    grid.set(x, y, if x < 2 { RED } else { BLUE });
+                }
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn write_then_parse_keeps_every_pixel() {
+        for size in Size::ALL {
+            let grid = striped_grid();
+            assert_eq!(
+                super::parse(&super::write(&grid, size), size),
+                grid,
+                "{size}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_then_write_then_parse_keeps_every_pixel() {
+        // older colorscripts wrote the full colors and a reset for every cell
+        let colorscript =
+            "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m \x1b[38;2;0;0;255m▄\x1b[0m\n";
+        let grid = super::parse(colorscript, Size::Small);
+
+        assert_eq!(
+            grid,
+            self::grid(3, 2, &[RED, CLEAR, CLEAR, BLUE, CLEAR, BLUE])
+        );
+        assert_eq!(
+            super::parse(&super::write(&grid, Size::Small), Size::Small),
+            grid
+        );
+    }
+
+    #[test]
+    fn only_writes_colors_that_change() {
+        let cases = [
+            // same colors all along the line
+            (
+                grid(3, 2, &[RED; 6]),
+                "\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m▀▀▀\x1b[0m\n",
+            ),
+            // a background can only be taken away with a reset, the foreground is written again after it
+            (
+                grid(2, 2, &[RED, RED, BLUE, CLEAR]),
+                "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\x1b[38;2;255;0;0m▀\x1b[0m\n",
+            ),
+            // transparent cells do not need any colors
+            (grid(2, 2, &[CLEAR; 4]), "  \n"),
+        ];
+
+        for (grid, colorscript) in cases {
+            assert_eq!(super::write(&grid, Size::Small), colorscript);
+        }
+    }
+
+    #[test]
+    fn writes_big_with_two_glyphs_for_every_pixel() {
+        let grid = grid(4, 1, &[RED, RED, CLEAR, BLUE]);
+
+        assert_eq!(
+            super::write(&grid, Size::Big),
+            "\x1b[38;2;255;0;0m████  \x1b[38;2;0;0;255m██\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn writes_ascii_from_the_ramp() {
+        let grid = grid(2, 2, &[WHITE, CLEAR, WHITE, CLEAR]);
+        let ramp = crate::constants::DEFAULT_RAMP;
+
+        assert_eq!(
+            super::write_ascii(&grid, Size::Small, ramp),
+            "\x1b[38;2;255;255;255m@ \x1b[0m\n"
+        );
+        // big keeps two characters for every pixel
+        assert_eq!(
+            super::write_ascii(&grid, Size::Big, ramp),
+            "\x1b[38;2;255;255;255m@@  \x1b[0m\n\x1b[38;2;255;255;255m@@  \x1b[0m\n"
+        );
+        // without colors to show, transparent pixels get the first character of the ramp
+        assert_eq!(
+            super::write_ascii(&grid, Size::Small, "_#"),
+            "\x1b[38;2;255;255;255m#_\x1b[0m\n"
+        );
+    }
+}
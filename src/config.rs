@@ -0,0 +1,619 @@
+/// Path to the config file, usually `~/.config/rustmon/config.toml`
+pub fn config_file_path() -> std::path::PathBuf {
+    crate::constants::CONFIG_DIRECTORY.join("config.toml")
+}
+
+/// Read the config file, a missing config file is the same as an empty one
+pub fn read_config() -> Result<crate::structs::Config, Box<dyn std::error::Error>> {
+    let file_path = config_file_path();
+
+    if !file_path.exists() {
+        return Ok(crate::structs::Config::default());
+    }
+
+    let contents = std::fs::read_to_string(&file_path)?;
+
+    // deserialize into the struct
+    match toml::from_str(&contents) {
+        Ok(config) => Ok(config),
+        Err(e) => Err(format!("Failed to parse {}: {}", file_path.display(), e).into()),
+    }
+}
+
+/// Read the config file as a document, which keeps its comments and unknown keys when it is changed and written back
+pub fn read_config_document() -> Result<toml_edit::DocumentMut, Box<dyn std::error::Error>> {
+    let file_path = config_file_path();
+
+    if !file_path.exists() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+
+    let contents = std::fs::read_to_string(&file_path)?;
+
+    match contents.parse::<toml_edit::DocumentMut>() {
+        Ok(document) => Ok(document),
+        Err(e) => Err(format!("Failed to parse {}: {}", file_path.display(), e).into()),
+    }
+}
+
+/// Write a config document back, creating the config directory if needed
+pub fn write_config_document(
+    document: &toml_edit::DocumentMut,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(&*crate::constants::CONFIG_DIRECTORY)?;
+    std::fs::write(config_file_path(), document.to_string())?;
+
+    Ok(())
+}
+
+/// Expand aliases in a list of name tokens
+/// anything after the alias, e.g. `:shiny` in `sparky:shiny`, is kept on the expanded selector
+pub fn expand_aliases(
+    tokens: &[&String],
+    aliases: &std::collections::BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut expanded: Vec<String> = Vec::new();
+
+    for token in tokens {
+        let (base, extras) = match token.split_once(':') {
+            Some((base, extras)) => (base, Some(extras)),
+            None => (token.as_str(), None),
+        };
+
+        // aliases are matched the same way as names
+        let query = crate::search::normalize(base);
+        match aliases
+            .iter()
+            .find(|(alias, _)| crate::search::normalize(alias) == query)
+        {
+            Some((_, selector)) => {
+                let mut alias_tokens: Vec<String> =
+                    selector.split_whitespace().map(String::from).collect();
+                if let (Some(extras), Some(last)) = (extras, alias_tokens.last_mut()) {
+                    last.push(':');
+                    last.push_str(extras);
+                }
+                expanded.extend(alias_tokens);
+            }
+            None => expanded.push(token.to_string()),
+        }
+    }
+
+    expanded
+}
+
+/// Get the list of name tokens saved in a team
+pub fn team_tokens(team: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let config = read_config()?;
+
+    match config.teams.get(team) {
+        Some(selectors) if !selectors.is_empty() => Ok(selectors
+            .iter()
+            .flat_map(|selector| selector.split_whitespace().map(String::from))
+            .collect()),
+        Some(_) => Err(format!("Team {} is empty", team).into()),
+        None => Err(format!(
+            "Team {} not found\nHint: Do `rustmon team list` to see all saved teams.",
+            team
+        )
+        .into()),
+    }
+}
+
+/// A resolved setting, and where it came from
+pub struct Setting<T> {
+    pub value: T,
+    pub source: String,
+}
+
+pub struct PrintSettings {
+    pub align: Setting<crate::structs::HorizontalAlignment>,
+    pub background: Setting<Option<crate::structs::Rgb>>,
+    pub charset: Setting<crate::structs::Charset>,
+    pub columns: Setting<Option<u16>>,
+    pub daltonize: Setting<Option<crate::structs::ColorDeficiency>>,
+    pub filter: Setting<crate::structs::Filter>,
+    pub height: Setting<Option<u16>>,
+    pub hide_name: Setting<bool>,
+    pub label: Setting<String>,
+    pub label_color: Setting<bool>,
+    pub label_position: Setting<crate::structs::LabelPosition>,
+    pub lang: Setting<String>,
+    pub margin: Setting<u8>,
+    pub padding: Setting<u8>,
+    pub protocol: Setting<crate::structs::Protocol>,
+    pub ramp: Setting<String>,
+    pub scale: Setting<u8>,
+    pub shiny: Setting<f32>,
+    pub shiny_indicator: Setting<bool>,
+    pub size: Setting<crate::structs::Size>,
+    pub spacing: Setting<u8>,
+    pub valign: Setting<crate::structs::VerticalAlignment>,
+}
+
+pub struct FetchSettings {
+    pub alpha_threshold: Setting<u8>,
+    pub blend: Setting<Option<crate::structs::Rgb>>,
+    pub extract_destination: Setting<String>,
+    pub heights: Setting<Vec<u16>>,
+    pub verbose: Setting<bool>,
+}
+
+/// Resolve the print settings
+/// command line flags win over the profile, which wins over the config file, which wins over the defaults
+pub fn resolve_print_settings(
+    print_args: Option<&clap::ArgMatches>,
+    config: &crate::structs::Config,
+    profile: Option<&String>,
+) -> Result<PrintSettings, Box<dyn std::error::Error>> {
+    let profile_defaults = get_profile(config, profile)?.map(|p| &p.print);
+
+    Ok(PrintSettings {
+        align: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::HorizontalAlignment>("align")
+                    .copied()
+            }),
+            profile_defaults.and_then(|p| p.align),
+            config.print.align,
+            profile,
+            crate::structs::HorizontalAlignment::Left,
+        ),
+        // only in the config file, the terminal is asked first
+        background: pick(
+            None,
+            profile_defaults.and_then(|p| p.background.map(Some)),
+            config.print.background.map(Some),
+            profile,
+            None,
+        ),
+        charset: pick(
+            print_args.and_then(|args| args.get_one::<crate::structs::Charset>("charset").copied()),
+            profile_defaults.and_then(|p| p.charset),
+            config.print.charset,
+            profile,
+            crate::structs::Charset::Unicode,
+        ),
+        columns: pick(
+            print_args.and_then(|args| args.get_one::<u16>("columns").map(|c| Some(*c))),
+            profile_defaults.and_then(|p| p.columns.map(Some)),
+            config.print.columns.map(Some),
+            profile,
+            None,
+        ),
+        daltonize: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::ColorDeficiency>("daltonize")
+                    .map(|deficiency| Some(*deficiency))
+            }),
+            profile_defaults.and_then(|p| p.daltonize.map(Some)),
+            config.print.daltonize.map(Some),
+            profile,
+            None,
+        ),
+        filter: pick(
+            print_args.and_then(|args| args.get_one::<crate::structs::Filter>("filter").copied()),
+            profile_defaults.and_then(|p| p.filter),
+            config.print.filter,
+            profile,
+            crate::structs::Filter::Triangle,
+        ),
+        height: pick(
+            print_args.and_then(|args| args.get_one::<u16>("height").map(|h| Some(*h))),
+            profile_defaults.and_then(|p| p.height.map(Some)),
+            config.print.height.map(Some),
+            profile,
+            None,
+        ),
+        hide_name: pick(
+            print_args.and_then(|args| args.get_flag("hide-name").then_some(true)),
+            profile_defaults.and_then(|p| p.hide_name),
+            config.print.hide_name,
+            profile,
+            false,
+        ),
+        label: pick(
+            print_args.and_then(|args| args.get_one::<String>("label").cloned()),
+            profile_defaults.and_then(|p| p.label.clone()),
+            config.print.label.clone(),
+            profile,
+            crate::constants::DEFAULT_LABEL.to_string(),
+        ),
+        label_color: pick(
+            print_args.and_then(|args| args.get_flag("label-color").then_some(true)),
+            profile_defaults.and_then(|p| p.label_color),
+            config.print.label_color,
+            profile,
+            false,
+        ),
+        label_position: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::LabelPosition>("label-position")
+                    .copied()
+            }),
+            profile_defaults.and_then(|p| p.label_position),
+            config.print.label_position,
+            profile,
+            crate::structs::LabelPosition::Above,
+        ),
+        lang: pick(
+            print_args.and_then(|args| args.get_one::<String>("lang").cloned()),
+            profile_defaults.and_then(|p| p.lang.clone()),
+            config.print.lang.clone(),
+            profile,
+            crate::constants::DEFAULT_LANGUAGE.to_string(),
+        ),
+        margin: pick(
+            print_args.and_then(|args| args.get_one::<u8>("margin").copied()),
+            profile_defaults.and_then(|p| p.margin),
+            config.print.margin,
+            profile,
+            0,
+        ),
+        padding: pick(
+            print_args.and_then(|args| args.get_one::<u8>("padding").copied()),
+            profile_defaults.and_then(|p| p.padding),
+            config.print.padding,
+            profile,
+            0,
+        ),
+        protocol: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::Protocol>("protocol")
+                    .copied()
+            }),
+            profile_defaults.and_then(|p| p.protocol),
+            config.print.protocol,
+            profile,
+            crate::structs::Protocol::Blocks,
+        ),
+        ramp: pick(
+            print_args.and_then(|args| args.get_one::<String>("ramp").cloned()),
+            profile_defaults.and_then(|p| p.ramp.clone()),
+            config.print.ramp.clone(),
+            profile,
+            crate::constants::DEFAULT_RAMP.to_string(),
+        ),
+        scale: pick(
+            print_args.and_then(|args| args.get_one::<u8>("scale").copied()),
+            profile_defaults.and_then(|p| p.scale),
+            config.print.scale,
+            profile,
+            1,
+        ),
+        shiny: pick(
+            print_args.and_then(|args| args.get_one::<f32>("shiny").copied()),
+            profile_defaults.and_then(|p| p.shiny),
+            config.print.shiny,
+            profile,
+            crate::constants::DEFAULT_SHINY_RATE,
+        ),
+        // on by default with `daltonize`, which is meant for the same people
+        shiny_indicator: pick(
+            print_args.and_then(|args| args.get_flag("shiny-indicator").then_some(true)),
+            profile_defaults.and_then(|p| p.shiny_indicator),
+            config.print.shiny_indicator,
+            profile,
+            false,
+        ),
+        // `big` is the older way of picking the size
+        size: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::Size>("size")
+                    .copied()
+                    .or(args.get_flag("big").then_some(crate::structs::Size::Big))
+            }),
+            profile_defaults.and_then(|p| p.size.or(p.big.map(size_from_big))),
+            config.print.size.or(config.print.big.map(size_from_big)),
+            profile,
+            crate::structs::Size::Small,
+        ),
+        spacing: pick(
+            print_args.and_then(|args| args.get_one::<u8>("spacing").copied()),
+            profile_defaults.and_then(|p| p.spacing),
+            config.print.spacing,
+            profile,
+            crate::constants::DEFAULT_SPACING,
+        ),
+        valign: pick(
+            print_args.and_then(|args| {
+                args.get_one::<crate::structs::VerticalAlignment>("valign")
+                    .copied()
+            }),
+            profile_defaults.and_then(|p| p.valign),
+            config.print.valign,
+            profile,
+            crate::structs::VerticalAlignment::Top,
+        ),
+    })
+}
+
+/// Resolve the fetch settings, in the same order as the print settings
+pub fn resolve_fetch_settings(
+    fetch_args: Option<&clap::ArgMatches>,
+    config: &crate::structs::Config,
+    profile: Option<&String>,
+) -> Result<FetchSettings, Box<dyn std::error::Error>> {
+    let profile_defaults = get_profile(config, profile)?.map(|p| &p.fetch);
+
+    Ok(FetchSettings {
+        alpha_threshold: pick(
+            fetch_args.and_then(|args| args.get_one::<u8>("alpha_threshold").copied()),
+            profile_defaults.and_then(|p| p.alpha_threshold),
+            config.fetch.alpha_threshold,
+            profile,
+            crate::constants::DEFAULT_ALPHA_THRESHOLD,
+        ),
+        blend: pick(
+            fetch_args.and_then(|args| {
+                args.get_one::<crate::structs::Rgb>("blend")
+                    .map(|color| Some(*color))
+            }),
+            profile_defaults.and_then(|p| p.blend.map(Some)),
+            config.fetch.blend.map(Some),
+            profile,
+            None,
+        ),
+        extract_destination: pick(
+            fetch_args.and_then(|args| args.get_one::<String>("extract_destination").cloned()),
+            profile_defaults.and_then(|p| p.extract_destination.clone()),
+            config.fetch.extract_destination.clone(),
+            profile,
+            crate::constants::DATA_DIRECTORY
+                .to_str()
+                .unwrap()
+                .to_string(),
+        ),
+        heights: pick(
+            fetch_args.and_then(|args| {
+                args.get_many::<u16>("heights")
+                    .map(|heights| heights.copied().collect())
+            }),
+            profile_defaults.and_then(|p| p.heights.clone()),
+            config.fetch.heights.clone(),
+            profile,
+            vec![],
+        ),
+        verbose: pick(
+            fetch_args.and_then(|args| args.get_flag("verbose").then_some(true)),
+            profile_defaults.and_then(|p| p.verbose),
+            config.fetch.verbose,
+            profile,
+            false,
+        ),
+    })
+}
+
+/// Print the effective settings and where each one came from
+pub fn show(profile: Option<&String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = read_config()?;
+    let print_settings = resolve_print_settings(None, &config, profile)?;
+    let fetch_settings = resolve_fetch_settings(None, &config, profile)?;
+
+    println!("Config file: {}", config_file_path().display());
+    match profile {
+        Some(profile) => println!("Profile: {}", profile),
+        None => println!("Profile: none"),
+    }
+
+    println!("\n[print]");
+    print_setting("align", &print_settings.align);
+    print_setting(
+        "background",
+        &Setting {
+            value: match print_settings.background.value {
+                Some(color) => format!("{:?}", color.to_string()),
+                None => "none".to_string(),
+            },
+            source: print_settings.background.source,
+        },
+    );
+    print_setting("charset", &print_settings.charset);
+    print_setting(
+        "columns",
+        &Setting {
+            value: match print_settings.columns.value {
+                Some(columns) => columns.to_string(),
+                None => "auto".to_string(),
+            },
+            source: print_settings.columns.source,
+        },
+    );
+    print_setting(
+        "daltonize",
+        &Setting {
+            value: match print_settings.daltonize.value {
+                Some(deficiency) => deficiency.to_string(),
+                None => "none".to_string(),
+            },
+            source: print_settings.daltonize.source,
+        },
+    );
+    print_setting("filter", &print_settings.filter);
+    print_setting(
+        "height",
+        &Setting {
+            value: match print_settings.height.value {
+                Some(height) => height.to_string(),
+                None => "none".to_string(),
+            },
+            source: print_settings.height.source,
+        },
+    );
+    print_setting("hide_name", &print_settings.hide_name);
+    print_setting(
+        "label",
+        &Setting {
+            value: format!("{:?}", print_settings.label.value),
+            source: print_settings.label.source,
+        },
+    );
+    print_setting("label_color", &print_settings.label_color);
+    print_setting("label_position", &print_settings.label_position);
+    print_setting(
+        "lang",
+        &Setting {
+            value: format!("{:?}", print_settings.lang.value),
+            source: print_settings.lang.source,
+        },
+    );
+    print_setting("margin", &print_settings.margin);
+    print_setting("padding", &print_settings.padding);
+    print_setting("protocol", &print_settings.protocol);
+    print_setting(
+        "ramp",
+        &Setting {
+            value: format!("{:?}", print_settings.ramp.value),
+            source: print_settings.ramp.source,
+        },
+    );
+    print_setting("scale", &print_settings.scale);
+    print_setting("shiny", &print_settings.shiny);
+    print_setting("shiny_indicator", &print_settings.shiny_indicator);
+    print_setting("size", &print_settings.size);
+    print_setting("spacing", &print_settings.spacing);
+    print_setting("valign", &print_settings.valign);
+
+    // `say` follows the print settings above, except for these
+    println!("\n[say]");
+    print_setting(
+        "label",
+        &Setting {
+            value: format!("{:?}", crate::constants::SAY_LABEL),
+            source: "say".to_string(),
+        },
+    );
+    print_setting(
+        "label_position",
+        &Setting {
+            value: crate::structs::LabelPosition::Below,
+            source: "say".to_string(),
+        },
+    );
+    print_setting(
+        "protocol",
+        &Setting {
+            value: crate::structs::Protocol::Blocks,
+            source: "say".to_string(),
+        },
+    );
+    print_setting(
+        "spacing",
+        &Setting {
+            value: 0,
+            source: "say".to_string(),
+        },
+    );
+    println!("# everything else follows [print]");
+
+    println!("\n[fetch]");
+    print_setting("alpha_threshold", &fetch_settings.alpha_threshold);
+    print_setting(
+        "blend",
+        &Setting {
+            value: match fetch_settings.blend.value {
+                Some(color) => format!("{:?}", color.to_string()),
+                None => "none".to_string(),
+            },
+            source: fetch_settings.blend.source,
+        },
+    );
+    print_setting(
+        "extract_destination",
+        &Setting {
+            value: format!("{:?}", fetch_settings.extract_destination.value),
+            source: fetch_settings.extract_destination.source,
+        },
+    );
+    print_setting(
+        "heights",
+        &Setting {
+            value: format!("{:?}", fetch_settings.heights.value),
+            source: fetch_settings.heights.source,
+        },
+    );
+    print_setting("verbose", &fetch_settings.verbose);
+
+    if !config.profiles.is_empty() {
+        println!(
+            "\nAvailable profiles: {}",
+            config
+                .profiles
+                .keys()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    println!("\nHint: Command line flags always take priority over these settings.");
+
+    Ok(())
+}
+
+fn print_setting<T: std::fmt::Display>(key: &str, setting: &Setting<T>) {
+    println!(
+        "{:<24} # {}",
+        format!("{} = {}", key, setting.value),
+        setting.source
+    );
+}
+
+fn size_from_big(big: bool) -> crate::structs::Size {
+    if big {
+        crate::structs::Size::Big
+    } else {
+        crate::structs::Size::Small
+    }
+}
+
+fn get_profile<'a>(
+    config: &'a crate::structs::Config,
+    profile: Option<&String>,
+) -> Result<Option<&'a crate::structs::Profile>, Box<dyn std::error::Error>> {
+    match profile {
+        Some(profile) => match config.profiles.get(profile) {
+            Some(profile) => Ok(Some(profile)),
+            None => Err(format!(
+                "Profile {} not found in {}",
+                profile,
+                config_file_path().display()
+            )
+            .into()),
+        },
+        None => Ok(None),
+    }
+}
+
+fn pick<T>(
+    command_line: Option<T>,
+    profile_value: Option<T>,
+    config_value: Option<T>,
+    profile: Option<&String>,
+    default: T,
+) -> Setting<T> {
+    if let Some(value) = command_line {
+        return Setting {
+            value,
+            source: "command line".to_string(),
+        };
+    }
+
+    if let (Some(value), Some(profile)) = (profile_value, profile) {
+        return Setting {
+            value,
+            source: format!("profile {}", profile),
+        };
+    }
+
+    match config_value {
+        Some(value) => Setting {
+            value,
+            source: "config file".to_string(),
+        },
+        None => Setting {
+            value: default,
+            source: "default".to_string(),
+        },
+    }
+}
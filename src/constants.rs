@@ -1,6 +1,39 @@
 pub const TARGET_URL: &str =
     "https://github.com/Vomitblood/pokesprite/archive/refs/heads/master.zip";
 
+// version of the colorscript encoding written by `fetch`
+// 2: colors are only written when they change
+pub const COLORSCRIPT_ENCODING_VERSION: u8 = 2;
+
+// defaults used when neither the command line nor the config file say otherwise
+pub const DEFAULT_SHINY_RATE: f32 = 0.0;
+pub const DEFAULT_SPACING: u8 = 4;
+pub const DEFAULT_LABEL: &str = "{name} {form} {shiny:✨}";
+// label of the speaker of `say`, which always goes below it
+pub const SAY_LABEL: &str = "{name} {form}";
+// added to the labels of shiny colorscripts by `--shiny-indicator`
+pub const SHINY_INDICATOR: &str = "[shiny]";
+pub const DEFAULT_LANGUAGE: &str = "eng";
+// pixels less opaque than this are left out, the rest are drawn fully opaque
+pub const DEFAULT_ALPHA_THRESHOLD: u8 = 1;
+pub const DEFAULT_SILHOUETTE_COLOR: &str = "#000000";
+pub const SHADOW_COLOR: crate::structs::Rgb = crate::structs::Rgb(0x3a, 0x3a, 0x3a);
+// background assumed by `--light-bg` if the terminal does not tell and the config file does not say
+pub const LIGHT_BACKGROUND: crate::structs::Rgb = crate::structs::Rgb(0xff, 0xff, 0xff);
+// smallest difference in brightness from the background that `--light-bg` allows, from 0 to 1
+pub const MIN_CONTRAST: f32 = 0.25;
+// milliseconds to wait for the terminal to answer a query, e.g. for its background color
+pub const TERMINAL_QUERY_TIMEOUT: u64 = 100;
+// size of a terminal cell in pixels, if the terminal does not say
+pub const DEFAULT_CELL_SIZE: (usize, usize) = (10, 20);
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+// size of a sprite pixel in svg exports, in screen pixels
+pub const SVG_PIXEL_SIZE: usize = 4;
+// size of the svg label text, in sprite pixels
+pub const SVG_FONT_SIZE: usize = 6;
+// color of the labels on png sheets, readable on light and dark backgrounds
+pub const SHEET_LABEL_COLOR: crate::structs::Rgb = crate::structs::Rgb(0x80, 0x80, 0x80);
+
 pub static DATA_DIRECTORY: once_cell::sync::Lazy<std::path::PathBuf> =
     once_cell::sync::Lazy::new(|| {
         dirs::data_dir()
@@ -15,6 +48,13 @@ pub static CACHE_DIRECTORY: once_cell::sync::Lazy<std::path::PathBuf> =
             .expect("Cache directory not found")
     });
 
+pub static CONFIG_DIRECTORY: once_cell::sync::Lazy<std::path::PathBuf> =
+    once_cell::sync::Lazy::new(|| {
+        dirs::config_dir()
+            .map(|dir| dir.join("rustmon"))
+            .expect("Config directory not found")
+    });
+
 // pub const GENERATIONS: [(&str, (u16, u16)); 8] = [
 //     ("1", (1, 151)),
 //     ("2", (152, 251)),
@@ -0,0 +1,233 @@
+/// Apply the effects of the print options to a pixel grid
+/// mirroring comes first, then the silhouette, the outline and the shadow, so the outline and shadow keep their own colors
+pub fn apply(
+    grid: &crate::structs::PixelGrid,
+    effects: &crate::structs::Effects,
+) -> crate::structs::PixelGrid {
+    let mut grid = grid.clone();
+
+    if effects.flip {
+        grid = flip(&grid);
+    }
+    if let Some(color) = effects.silhouette {
+        grid = silhouette(&grid, color);
+    }
+    if let Some(color) = effects.outline {
+        grid = outline(&grid, color);
+    }
+    if effects.shadow {
+        grid = shadow(&grid, crate::constants::SHADOW_COLOR);
+    }
+
+    grid
+}
+
+/// Width and height of a pixel grid after the effects, without applying them
+/// the outline grows the grid by a pixel on every side, the shadow by one down and to the right
+pub fn dimensions(
+    width: usize,
+    height: usize,
+    effects: &crate::structs::Effects,
+) -> (usize, usize) {
+    let mut growth = 0;
+    if effects.outline.is_some() {
+        growth += 2;
+    }
+    if effects.shadow {
+        growth += 1;
+    }
+
+    (width + growth, height + growth)
+}
+
+/// Mirror a pixel grid horizontally
+pub fn flip(grid: &crate::structs::PixelGrid) -> crate::structs::PixelGrid {
+    let mut flipped_grid = crate::structs::PixelGrid::new(grid.width, grid.height);
+
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            flipped_grid.set(grid.width - 1 - x, y, grid.get(x, y));
+        }
+    }
+
+    flipped_grid
+}
+
+/// Paint every opaque pixel in one color
+pub fn silhouette(
+    grid: &crate::structs::PixelGrid,
+    color: crate::structs::Rgb,
+) -> crate::structs::PixelGrid {
+    let mut silhouette_grid = grid.clone();
+
+    for pixel in silhouette_grid.pixels.iter_mut() {
+        if pixel[3] != 0 {
+            *pixel = color.to_pixel();
+        }
+    }
+
+    silhouette_grid
+}
+
+/// Draw a line of pixels around the opaque ones, the grid grows by a pixel on every side
+/// only the pixels above, below and to the sides count, so corners stay round
+pub fn outline(
+    grid: &crate::structs::PixelGrid,
+    color: crate::structs::Rgb,
+) -> crate::structs::PixelGrid {
+    let mut outlined_grid = crate::structs::PixelGrid::new(grid.width + 2, grid.height + 2);
+
+    for y in 0..outlined_grid.height {
+        for x in 0..outlined_grid.width {
+            // position in the original grid, shifted by the new border
+            let opaque = |dx: isize, dy: isize| {
+                let (Some(x), Some(y)) = (
+                    (x as isize + dx - 1).try_into().ok(),
+                    (y as isize + dy - 1).try_into().ok(),
+                ) else {
+                    return false;
+                };
+                grid.get(x, y)[3] != 0
+            };
+
+            if opaque(0, 0) {
+                outlined_grid.set(x, y, grid.get(x - 1, y - 1));
+            } else if opaque(-1, 0) || opaque(1, 0) || opaque(0, -1) || opaque(0, 1) {
+                outlined_grid.set(x, y, color.to_pixel());
+            }
+        }
+    }
+
+    outlined_grid
+}
+
+/// Cast a shadow of the opaque pixels a pixel down and to the right, the grid grows by a pixel on those sides
+pub fn shadow(
+    grid: &crate::structs::PixelGrid,
+    color: crate::structs::Rgb,
+) -> crate::structs::PixelGrid {
+    let mut shadowed_grid = crate::structs::PixelGrid::new(grid.width + 1, grid.height + 1);
+
+    for y in 0..shadowed_grid.height {
+        for x in 0..shadowed_grid.width {
+            let pixel = grid.get(x, y);
+            if pixel[3] != 0 {
+                shadowed_grid.set(x, y, pixel);
+            } else if x > 0 && y > 0 && grid.get(x - 1, y - 1)[3] != 0 {
+                shadowed_grid.set(x, y, color.to_pixel());
+            }
+        }
+    }
+
+    shadowed_grid
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::{PixelGrid, Rgb};
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+    const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn grid(width: usize, height: usize, pixels: &[[u8; 4]]) -> PixelGrid {
+        PixelGrid {
+            width,
+            height,
+            pixels: pixels.to_vec(),
+        }
+    }
+
+    // an L of red and blue with holes, so nothing lines up with itself when mirrored
+    fn uneven_grid() -> PixelGrid {
+        grid(
+            3,
+            3,
+            &[RED, CLEAR, CLEAR, RED, CLEAR, BLUE, RED, BLUE, BLUE],
+        )
+    }
+
+    #[test]
+    fn flip_twice_gives_back_the_grid() {
+        let grid = uneven_grid();
+        let flipped_grid = super::flip(&grid);
+
+        assert_ne!(flipped_grid, grid);
+        assert_eq!(super::flip(&flipped_grid), grid);
+    }
+
+    #[test]
+    fn silhouette_keeps_the_alpha() {
+        let grid = uneven_grid();
+        let silhouette_grid = super::silhouette(&grid, WHITE);
+
+        for (pixel, silhouette_pixel) in grid.pixels.iter().zip(&silhouette_grid.pixels) {
+            assert_eq!(pixel[3], silhouette_pixel[3]);
+            if pixel[3] != 0 {
+                assert_eq!(*silhouette_pixel, WHITE.to_pixel());
+            }
+        }
+    }
+
+    #[test]
+    fn outline_only_adds_pixels_next_to_opaque_ones() {
+        let grid = uneven_grid();
+        let outlined_grid = super::outline(&grid, WHITE);
+
+        assert_eq!(
+            (outlined_grid.width, outlined_grid.height),
+            (grid.width + 2, grid.height + 2)
+        );
+        for y in 0..outlined_grid.height {
+            for x in 0..outlined_grid.width {
+                // the same pixel in the original grid, transparent on the new border
+                let original = |dx: isize, dy: isize| match (
+                    (x as isize + dx - 1).try_into(),
+                    (y as isize + dy - 1).try_into(),
+                ) {
+                    (Ok(x), Ok(y)) => grid.get(x, y),
+                    _ => CLEAR,
+                };
+
+                if original(0, 0)[3] != 0 {
+                    assert_eq!(outlined_grid.get(x, y), original(0, 0), "({x}, {y})");
+                    continue;
+                }
+                let next_to_opaque = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .any(|(dx, dy)| original(*dx, *dy)[3] != 0);
+                let expected = if next_to_opaque {
+                    WHITE.to_pixel()
+                } else {
+                    CLEAR
+                };
+                assert_eq!(outlined_grid.get(x, y), expected, "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn shadow_falls_a_pixel_down_and_right() {
+        let shadow = crate::constants::SHADOW_COLOR.to_pixel();
+        let cases = [
+            (grid(1, 1, &[RED]), grid(2, 2, &[RED, CLEAR, CLEAR, shadow])),
+            // the shadow stays behind the pixels it falls on
+            (
+                grid(2, 2, &[RED, CLEAR, CLEAR, BLUE]),
+                grid(
+                    3,
+                    3,
+                    &[RED, CLEAR, CLEAR, CLEAR, BLUE, CLEAR, CLEAR, CLEAR, shadow],
+                ),
+            ),
+        ];
+
+        for (grid, shadowed_grid) in cases {
+            assert_eq!(
+                super::shadow(&grid, crate::constants::SHADOW_COLOR),
+                shadowed_grid
+            );
+        }
+    }
+}
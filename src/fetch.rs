@@ -1,7 +1,12 @@
 use image::GenericImageView;
 use std::io::Write;
 
-pub fn fetch(extract_destination: &std::path::Path, verbose: bool) {
+pub fn fetch(
+    extract_destination: &std::path::Path,
+    heights: &[u16],
+    alpha: &crate::structs::AlphaOptions,
+    verbose: bool,
+) {
     // prep working directory
     match create_working_directory() {
         Ok(_) => (),
@@ -62,7 +67,7 @@ pub fn fetch(extract_destination: &std::path::Path, verbose: bool) {
     };
 
     // crop images to content
-    match crop_all_images_in_directory() {
+    match crop_all_images_in_directory(alpha.threshold) {
         Ok(_) => (),
         Err(e) => {
             eprintln!("Error cropping images: {}", e);
@@ -71,8 +76,8 @@ pub fn fetch(extract_destination: &std::path::Path, verbose: bool) {
         }
     };
 
-    // convert images to unicode, both small and big
-    match convert_images_to_ascii(extract_destination, verbose) {
+    // convert images to unicode, in every size and every height asked for
+    match convert_images_to_ascii(extract_destination, heights, alpha, verbose) {
         Ok(_) => (),
         Err(e) => {
             eprintln!("Error converting images to ASCII: {}", e);
@@ -200,8 +205,8 @@ fn transform_pokemon_data(
     pokemon_collection: &std::collections::HashMap<String, crate::structs::PokemonRaw>,
 ) -> Vec<crate::structs::Pokemon> {
     let mut processed_pokemons: Vec<crate::structs::Pokemon> = pokemon_collection
-        .iter()
-        .map(|(_key, p)| {
+        .values()
+        .map(|p| {
             let mut forms = p
                 .gen_8
                 .forms
@@ -223,10 +228,13 @@ fn transform_pokemon_data(
             crate::structs::Pokemon {
                 // remove leading zeros from the pokedex number
                 pokedex: p.idx.trim_start_matches('0').to_string(),
-                // use the slug as the name
-                // this is because i am too lazy to decapitalize the name
-                // also in case of name =/= slug
+                // use the slug as the name, it is what the colorscripts are named after
                 name: p.slug.eng.clone(),
+                // keep the proper name for displaying
+                display_name: p.name.eng.clone(),
+                // names and slugs in other languages, for lookups and `--lang`
+                names: p.name.others.clone(),
+                slugs: p.slug.others.clone(),
                 forms,
             }
         })
@@ -347,7 +355,7 @@ fn extract_colorscripts_archive() -> zip::result::ZipResult<()> {
     Ok(())
 }
 
-fn crop_all_images_in_directory() -> std::io::Result<()> {
+fn crop_all_images_in_directory(alpha_threshold: u8) -> std::io::Result<()> {
     println!("Cropping images...");
 
     // make sure the cropped_images directory exists
@@ -375,7 +383,7 @@ fn crop_all_images_in_directory() -> std::io::Result<()> {
             let path = entry.path();
 
             let output_path = output_subdirectory_path.join(path.file_name().unwrap());
-            crop_to_content(&path, &output_path).unwrap();
+            crop_to_content(&path, &output_path, alpha_threshold).unwrap();
         }
     }
 
@@ -387,6 +395,7 @@ fn crop_all_images_in_directory() -> std::io::Result<()> {
 fn crop_to_content(
     input_path: &std::path::Path,
     output_path: &std::path::Path,
+    alpha_threshold: u8,
 ) -> image::ImageResult<image::DynamicImage> {
     // load the image
     let img = image::open(input_path)?;
@@ -400,8 +409,8 @@ fn crop_to_content(
     for y in 0..height {
         for x in 0..width {
             let pixel = img.get_pixel(x, y);
-            if pixel[3] != 0 {
-                // if pixel is not transparent
+            if pixel[3] != 0 && pixel[3] >= alpha_threshold {
+                // if pixel is opaque enough to be drawn
                 if x < min_x {
                     min_x = x;
                 }
@@ -441,19 +450,30 @@ fn crop_to_content(
 
 fn convert_images_to_ascii(
     output_directory_path: &std::path::Path,
+    heights: &[u16],
+    alpha: &crate::structs::AlphaOptions,
     verbose: bool,
 ) -> std::io::Result<()> {
     println!("Extract destination: {:?}", output_directory_path);
     println!("Converting images to ASCII...");
 
-    for size in ["small", "big"].iter() {
+    // every size gets its own directory, and so does every height of the fitted ones
+    let sizes = crate::structs::Size::ALL
+        .iter()
+        .map(|size| (None, *size))
+        .chain(
+            heights
+                .iter()
+                .flat_map(|height| crate::structs::Size::FITTED.map(|size| (Some(*height), size))),
+        );
+    for (height, size) in sizes {
         for subdirectory in ["regular", "shiny"].iter() {
             let input_subdirectory_path = &*crate::constants::CACHE_DIRECTORY
                 .join("cropped_images")
                 .join(subdirectory);
             let output_subdirectory_path = output_directory_path
                 .join("colorscripts")
-                .join(size)
+                .join(size.directory(height))
                 .join(subdirectory);
 
             std::fs::create_dir_all(&output_subdirectory_path)?;
@@ -464,11 +484,19 @@ fn convert_images_to_ascii(
 
                 if path.is_file() {
                     let img = image::open(&path).unwrap();
-                    let ascii_art = if *size == "small" {
-                        convert_image_to_unicode_small(&img)
-                    } else {
-                        convert_image_to_unicode_big(&img)
-                    };
+                    let mut grid = crate::colorscript::apply_alpha(
+                        &crate::colorscript::from_image(&img),
+                        alpha,
+                    );
+                    if let Some(height) = height {
+                        grid = crate::resize::fit_height(
+                            &grid,
+                            height,
+                            size,
+                            crate::structs::Filter::default(),
+                        );
+                    }
+                    let ascii_art = crate::colorscript::write(&grid, size);
 
                     // print for fun
                     if verbose {
@@ -483,85 +511,14 @@ fn convert_images_to_ascii(
         }
     }
 
+    // freshly converted colorscripts never need to be migrated
+    crate::migration::write_encoding_version(&output_directory_path.join("colorscripts"))?;
+
     println!("Converted images to ASCII");
 
     Ok(())
 }
 
-fn convert_image_to_unicode_small(img: &image::DynamicImage) -> String {
-    let mut unicode_sprite = String::new();
-    let (width, height) = img.dimensions();
-
-    for y in (0..height).step_by(2) {
-        for x in 0..width {
-            let upper_pixel = img.get_pixel(x, y);
-            let lower_pixel = if y + 1 < height {
-                img.get_pixel(x, y + 1)
-            } else {
-                // fallback to upper pixel if there's no lower pixel.
-                upper_pixel
-            };
-
-            if upper_pixel[3] == 0 && lower_pixel[3] == 0 {
-                unicode_sprite.push(' ');
-            } else if upper_pixel[3] == 0 {
-                unicode_sprite.push_str(&get_color_escape_code(lower_pixel, false));
-                unicode_sprite.push('▄');
-            } else if lower_pixel[3] == 0 {
-                unicode_sprite.push_str(&get_color_escape_code(upper_pixel, false));
-                unicode_sprite.push('▀');
-            } else {
-                unicode_sprite.push_str(&get_color_escape_code(upper_pixel, false));
-                unicode_sprite.push_str(&get_color_escape_code(lower_pixel, true));
-                unicode_sprite.push('▀');
-            }
-            unicode_sprite.push_str("\x1b[0m"); // Reset ANSI code after each character
-        }
-        unicode_sprite.push('\n'); // New line for each row, plus reset might be added here too if colors extend beyond.
-    }
-
-    unicode_sprite
-}
-
-fn convert_image_to_unicode_big(img: &image::DynamicImage) -> String {
-    let mut unicode_sprite = String::new();
-    let (width, height) = img.dimensions();
-
-    for y in 0..height {
-        for x in 0..width {
-            let pixel = img.get_pixel(x, y);
-
-            if pixel[3] == 0 {
-                unicode_sprite.push_str("  ");
-            } else {
-                unicode_sprite.push_str(&get_color_escape_code(pixel, false));
-                unicode_sprite.push_str("██");
-            }
-        }
-        unicode_sprite.push('\n');
-    }
-
-    unicode_sprite
-}
-
-fn get_color_escape_code(pixel: image::Rgba<u8>, background: bool) -> String {
-    if pixel[3] == 0 {
-        return format!("{}", crossterm::style::ResetColor);
-    }
-
-    let color = crossterm::style::Color::Rgb {
-        r: pixel[0],
-        g: pixel[1],
-        b: pixel[2],
-    };
-
-    if background {
-        format!("{}", crossterm::style::SetBackgroundColor(color))
-    } else {
-        format!("{}", crossterm::style::SetForegroundColor(color))
-    }
-}
-
 fn cleanup() -> std::io::Result<()> {
     println!("Cleaning up...");
 
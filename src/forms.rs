@@ -0,0 +1,266 @@
+use std::io::Read;
+
+/// A Pokemon name, with the form picked up from natural language if there was one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    pub name: String,
+    pub form: Option<String>,
+    // forced shiny with a `:shiny` selector
+    pub shiny: bool,
+}
+
+// maximum number of words a single Pokemon name can span, e.g. `mr mime`, `mime jr` or `tapu koko`
+// no Pokemon name has more than two
+const MAX_NAME_WORDS: usize = 2;
+
+/// Map a natural language qualifier to the form key used in `pokemon.json`
+/// e.g. `alolan` to `alola`, `gigantamax` to `gmax`
+fn form_key(word: &str) -> Option<&'static str> {
+    match word {
+        "alola" | "alolan" => Some("alola"),
+        "galar" | "galarian" => Some("galar"),
+        "hisui" | "hisuian" => Some("hisui"),
+        "paldea" | "paldean" => Some("paldea"),
+        "gmax" | "gigantamax" | "gigamax" => Some("gmax"),
+        "mega" => Some("mega"),
+        "primal" => Some("primal"),
+        "totem" => Some("totem"),
+        _ => None,
+    }
+}
+
+/// Parse a list of space-separated name tokens into Pokemon names and forms
+/// understands things like `alolan raichu`, `mega charizard x`, `raichu-alola` and `mr mime`
+/// selectors can end with `:<form>` and `:shiny`, e.g. `raichu:alola:shiny`
+/// aliases, e.g. from the config file read by the caller, are expanded first
+/// names that cannot be recognised are passed through as is, so the caller can report them
+pub fn parse_names(
+    tokens: &[&String],
+    aliases: &std::collections::BTreeMap<String, String>,
+) -> Result<Vec<Selection>, Box<dyn std::error::Error>> {
+    // expand nicknames such as `sparky`
+    let tokens = crate::config::expand_aliases(tokens, aliases);
+
+    // read the file
+    let mut file = std::fs::File::open(crate::constants::DATA_DIRECTORY.join("pokemon.json"))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    // deserialize into the struct
+    let pokemons: Vec<crate::structs::Pokemon> = serde_json::from_str(&contents)?;
+
+    Ok(select(&tokens, &pokemons))
+}
+
+/// Recognise the names and forms in tokens, after aliases are expanded
+fn select(tokens: &[String], pokemons: &[crate::structs::Pokemon]) -> Vec<Selection> {
+    // normalized name and slug pairs for lookups, every name of a pokemon leads to its slug
+    let known_names: Vec<(String, &String)> = pokemons
+        .iter()
+        .flat_map(|pokemon| {
+            pokemon
+                .names()
+                .into_iter()
+                .map(move |name| (crate::search::normalize(name), &pokemon.name))
+        })
+        .collect();
+
+    // everything after the first `:` in every token, e.g. `alola:shiny` in `raichu:alola:shiny`
+    let token_extras: Vec<Vec<String>> = tokens
+        .iter()
+        .map(|token| {
+            token
+                .split(':')
+                .skip(1)
+                .map(crate::search::normalize)
+                .filter(|extra| !extra.is_empty())
+                .collect()
+        })
+        .collect();
+
+    // split every token into words, remembering which token each word came from
+    // so that `raichu-alola` and `raichu alola` can be told apart
+    let words: Vec<(usize, String)> = tokens
+        .iter()
+        .enumerate()
+        .flat_map(|(token_index, token)| {
+            let base = token.split(':').next().unwrap_or("");
+            base.split(['-', '_'])
+                .map(crate::search::normalize)
+                .filter(|word| !word.is_empty())
+                .map(move |word| (token_index, word))
+        })
+        .collect();
+
+    let mut selections: Vec<Selection> = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let mut qualifiers: Vec<String> = Vec::new();
+        let first_token = words[i].0;
+
+        // prefixes, e.g. `alolan` in `alolan raichu`
+        // a qualifier on its own at the end is treated as a name
+        while i + 1 < words.len() {
+            match form_key(&words[i].1) {
+                Some(key) => {
+                    qualifiers.push(key.to_string());
+                    i += 1;
+                }
+                None => break,
+            }
+        }
+
+        // find the longest run of words that makes up a known name
+        // a pokedex number on its own also works, e.g. `25:shiny`
+        let mut name = match pokemons
+            .iter()
+            .find(|pokemon| pokemon.pokedex == words[i].1)
+        {
+            Some(pokemon) => pokemon.name.clone(),
+            None => words[i].1.clone(),
+        };
+        let mut length = 1;
+        for span in (1..=MAX_NAME_WORDS.min(words.len() - i)).rev() {
+            let joined: String = words[i..i + span]
+                .iter()
+                .map(|(_, word)| word.as_str())
+                .collect();
+            if let Some((_, known_name)) = known_names.iter().find(|(n, _)| *n == joined) {
+                name = known_name.to_string();
+                length = span;
+                break;
+            }
+        }
+        let last_token = words[i + length - 1].0;
+        i += length;
+
+        // suffixes in the same token, e.g. `alola` in `raichu-alola` or `mega-x` in `charizard-mega-x`
+        while i < words.len() && words[i].0 == last_token {
+            let word = &words[i].1;
+            qualifiers.push(form_key(word).unwrap_or(word).to_string());
+            i += 1;
+        }
+
+        // `x` or `y` after a mega, e.g. `mega charizard x`
+        if qualifiers.last().is_some_and(|q| q == "mega")
+            && i < words.len()
+            && (words[i].1 == "x" || words[i].1 == "y")
+        {
+            qualifiers.push(words[i].1.clone());
+            i += 1;
+        }
+
+        let mut form = if qualifiers.is_empty() {
+            None
+        } else {
+            Some(qualifiers.join("-"))
+        };
+
+        // selector extras of every token this name spans
+        let end_token = words[i - 1].0;
+        let mut shiny = false;
+        for extra in token_extras[first_token..=end_token].iter().flatten() {
+            if extra == "shiny" {
+                shiny = true;
+            } else {
+                form = Some(form_key(extra).unwrap_or(extra).to_string());
+            }
+        }
+
+        selections.push(Selection { name, form, shiny });
+    }
+
+    selections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+
+    fn pokemon(pokedex: &str, name: &str, display_name: &str) -> crate::structs::Pokemon {
+        crate::structs::Pokemon {
+            pokedex: pokedex.to_string(),
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            names: Default::default(),
+            slugs: Default::default(),
+            forms: vec!["regular".to_string()],
+        }
+    }
+
+    fn selection(name: &str, form: Option<&str>, shiny: bool) -> Selection {
+        Selection {
+            name: name.to_string(),
+            form: form.map(|form| form.to_string()),
+            shiny,
+        }
+    }
+
+    #[test]
+    fn selects_names_and_forms() {
+        let pokemons = [
+            pokemon("6", "charizard", "Charizard"),
+            pokemon("25", "pikachu", "Pikachu"),
+            pokemon("26", "raichu", "Raichu"),
+            pokemon("122", "mr-mime", "Mr. Mime"),
+            pokemon("439", "mime-jr", "Mime Jr."),
+        ];
+
+        let cases = [
+            ("pikachu", vec![selection("pikachu", None, false)]),
+            (
+                "alolan raichu",
+                vec![selection("raichu", Some("alola"), false)],
+            ),
+            (
+                "raichu-alola",
+                vec![selection("raichu", Some("alola"), false)],
+            ),
+            // a suffix only counts in the same token
+            (
+                "raichu alola",
+                vec![
+                    selection("raichu", None, false),
+                    selection("alola", None, false),
+                ],
+            ),
+            (
+                "mega charizard x",
+                vec![selection("charizard", Some("mega-x"), false)],
+            ),
+            (
+                "charizard-mega-x",
+                vec![selection("charizard", Some("mega-x"), false)],
+            ),
+            (
+                "gigantamax pikachu",
+                vec![selection("pikachu", Some("gmax"), false)],
+            ),
+            ("Mr. Mime", vec![selection("mr-mime", None, false)]),
+            (
+                "mr mime mime jr",
+                vec![
+                    selection("mr-mime", None, false),
+                    selection("mime-jr", None, false),
+                ],
+            ),
+            (
+                "galarian mr mime",
+                vec![selection("mr-mime", Some("galar"), false)],
+            ),
+            ("25:shiny", vec![selection("pikachu", None, true)]),
+            (
+                "raichu:alolan:shiny",
+                vec![selection("raichu", Some("alola"), true)],
+            ),
+            // unknown names are passed through for the caller to report
+            ("missingno", vec![selection("missingno", None, false)]),
+        ];
+
+        for (input, expected) in cases {
+            let tokens: Vec<String> = input.split_whitespace().map(String::from).collect();
+            assert_eq!(super::select(&tokens, &pokemons), expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/// Render a label template for a sprite
+/// `{name}`, `{slug}`, `{dex}` and `{form}` are replaced with their values
+/// `{shiny}` becomes `shiny` for shiny sprites, `{shiny:text}` becomes `text` instead
+/// placeholders that end up empty leave no extra spaces behind
+pub fn render(template: &str, sprite: &crate::structs::Sprite) -> String {
+    let mut rendered = String::new();
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            rendered.push(c);
+            continue;
+        }
+
+        // read until the closing brace
+        let mut placeholder = String::new();
+        let mut closed = false;
+        for c in chars.by_ref() {
+            if c == '}' {
+                closed = true;
+                break;
+            }
+            placeholder.push(c);
+        }
+
+        if !closed {
+            // not a placeholder, keep it as is
+            rendered.push('{');
+            rendered.push_str(&placeholder);
+            break;
+        }
+
+        let (key, text) = match placeholder.split_once(':') {
+            Some((key, text)) => (key, Some(text)),
+            None => (placeholder.as_str(), None),
+        };
+
+        match key {
+            "name" => rendered.push_str(&sprite.name),
+            "slug" => rendered.push_str(
+                sprite
+                    .path
+                    .file_name()
+                    .and_then(|os_str| os_str.to_str())
+                    .unwrap_or(""),
+            ),
+            "dex" => rendered.push_str(&sprite.pokedex.to_string()),
+            "form" => {
+                if sprite.form != "regular" {
+                    rendered.push_str(&sprite.form);
+                }
+            }
+            "shiny" => {
+                if sprite.shiny {
+                    rendered.push_str(text.unwrap_or("shiny"));
+                }
+            }
+            // unknown placeholder, keep it as is
+            _ => {
+                rendered.push('{');
+                rendered.push_str(&placeholder);
+                rendered.push('}');
+            }
+        }
+    }
+
+    // collapse the spaces left behind by empty placeholders
+    rendered.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Add the shiny indicator to the label of a shiny sprite, unless the label already has it
+pub fn add_shiny_indicator(label: &str, sprite: &crate::structs::Sprite) -> String {
+    if !sprite.shiny || label.contains(crate::constants::SHINY_INDICATOR) {
+        return label.to_string();
+    }
+
+    format!("{} {}", label, crate::constants::SHINY_INDICATOR)
+        .trim()
+        .to_string()
+}
+
+/// Find the most common color in a colorscript, ignoring dark outline colors if possible
+pub fn dominant_color(lines: &[String]) -> Option<(u8, u8, u8)> {
+    let mut counts: std::collections::HashMap<(u8, u8, u8), usize> =
+        std::collections::HashMap::new();
+    for line in lines {
+        for cell in crate::colorscript::parse_cells(line) {
+            // the foreground of a blank cell is never seen
+            let foreground = cell.foreground.filter(|_| cell.glyph != ' ');
+            for color in [foreground, cell.background].into_iter().flatten() {
+                *counts.entry(color).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // outlines are usually the most common color, so prefer brighter colors
+    let bright_color = counts
+        .iter()
+        .filter(|((r, g, b), _)| (*r as u16 + *g as u16 + *b as u16) > 120)
+        .max_by_key(|(color, count)| (**count, **color))
+        .map(|(color, _)| *color);
+
+    bright_color.or_else(|| {
+        counts
+            .iter()
+            .max_by_key(|(color, count)| (**count, **color))
+            .map(|(color, _)| *color)
+    })
+}
+
+/// Wrap a label in the escape codes for a color
+pub fn colorize(label: &str, color: (u8, u8, u8), depth: crate::structs::ColorDepth) -> String {
+    format!(
+        "{}{}{}",
+        crate::color::escape_code(color, false, depth),
+        label,
+        crossterm::style::ResetColor
+    )
+}
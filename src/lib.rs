@@ -1,8 +1,22 @@
 pub mod args;
+pub mod color;
+pub mod colorscript;
+pub mod config;
 pub mod constants;
+pub mod effects;
 pub mod fetch;
+pub mod forms;
+pub mod label;
 pub mod list;
+pub mod markup;
+pub mod migration;
+pub mod palette;
 pub mod print;
+pub mod protocol;
+pub mod resize;
 pub mod say;
+pub mod search;
+pub mod sheet;
 pub mod structs;
+pub mod team;
 pub mod validation;
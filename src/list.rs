@@ -1,4 +1,4 @@
-pub fn print_pokemon_list() -> Result<(), serde_json::Error> {
+pub fn print_pokemon_list(lang: &str) -> Result<(), serde_json::Error> {
     // open the file in read only mode with buffer
     let file = std::fs::File::open(crate::constants::DATA_DIRECTORY.join("pokemon.json"))
         .expect("File not found");
@@ -9,7 +9,13 @@ pub fn print_pokemon_list() -> Result<(), serde_json::Error> {
 
     // iterate through the vector and print the pokedex and name
     for pokemon in pokemon_list {
-        println!("[{}] {}", pokemon.pokedex, pokemon.name);
+        // show the slug too if it is different, both work as input
+        let name = pokemon.get_name(lang);
+        if name == pokemon.name {
+            println!("[{}] {}", pokemon.pokedex, pokemon.name);
+        } else {
+            println!("[{}] {} ({})", pokemon.pokedex, name, pokemon.name);
+        }
     }
 
     println!("\nHint: Having trouble finding a Pokemon? Pass in --help to see tips!");
@@ -17,7 +23,10 @@ pub fn print_pokemon_list() -> Result<(), serde_json::Error> {
     Ok(())
 }
 
-pub fn print_pokemon_forms(pokemon_name: &str) -> std::io::Result<()> {
+pub fn print_pokemon_forms(
+    pokemon_name: &str,
+    lang: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
     // open the file in read only mode with buffer
     let file = std::fs::File::open(crate::constants::DATA_DIRECTORY.join("pokemon.json"))?;
     let reader = std::io::BufReader::new(file);
@@ -26,14 +35,37 @@ pub fn print_pokemon_forms(pokemon_name: &str) -> std::io::Result<()> {
     let pokemon_list: Vec<crate::structs::Pokemon> =
         serde_json::from_reader(reader).expect("Failed to parse JSON");
 
+    // understand names such as `alolan raichu`, the form is highlighted in the list
+    let tokens: Vec<String> = pokemon_name.split_whitespace().map(String::from).collect();
+    // `list` does not read the config file, so aliases are not expanded
+    let selections = crate::forms::parse_names(
+        &tokens.iter().collect::<Vec<&String>>(),
+        &std::collections::BTreeMap::new(),
+    )?;
+    let (query, requested_form) = match selections.as_slice() {
+        [selection] => (
+            crate::search::normalize(&selection.name),
+            selection.form.clone(),
+        ),
+        _ => (crate::search::normalize(pokemon_name), None),
+    };
+
     // iterate through the list to find the specified pokemon and print its forms
     let mut found = false;
-    for pokemon in pokemon_list {
-        // case insensitive comparison
-        if pokemon.name.eq_ignore_ascii_case(pokemon_name) {
-            println!("{} has the following forms:", pokemon.name);
-            for form in pokemon.forms {
-                println!(" - {}", form);
+    for pokemon in &pokemon_list {
+        // case and punctuation insensitive comparison
+        if pokemon
+            .names()
+            .iter()
+            .any(|name| crate::search::normalize(name) == query)
+        {
+            println!("{} has the following forms:", pokemon.get_name(lang));
+            for form in &pokemon.forms {
+                if requested_form.as_ref() == Some(form) {
+                    println!(" - {} (matches '{}')", form, pokemon_name);
+                } else {
+                    println!(" - {}", form);
+                }
             }
             found = true;
             break;
@@ -42,6 +74,16 @@ pub fn print_pokemon_forms(pokemon_name: &str) -> std::io::Result<()> {
 
     if !found {
         println!("No Pokemon found with the name '{}'.", pokemon_name);
+
+        // offer the closest names
+        let suggestions = crate::search::suggest(
+            pokemon_name,
+            pokemon_list.iter().map(|pokemon| pokemon.name.as_str()),
+        );
+        if !suggestions.is_empty() {
+            println!("{}", crate::search::format_suggestions(&suggestions));
+        }
+
         println!("Hint: Do `rustmon list` to see all available Pokemon.")
     } else {
         println!("\nHint: Pass in `--form` when using subcommand `print` to see the specific form of a Pokemon!");
@@ -1,42 +1,135 @@
 /*
 # Arguments
+- `color` - When to use colors: `auto`, `always` or `never`. `auto` checks for a terminal, TERM=dumb, NO_COLOR and CLICOLOR_FORCE.
+- `color-depth` - Number of colors the terminal can show: `auto`, `truecolor`, `256` or `16`. `auto` checks COLORTERM and TERM.
+- `dither` - Use ordered dithering when reducing colors
+- `profile` - Use a profile from the config file for default options
+
+## `config` - Inspect the config file
+- `show` - Print the effective settings and where each one came from
+
+## `export` - Export Pokemon colorscripts as a file
+Takes the same arguments as `print`, and:
+- `all-forms` - Put every form on the sheet, not only the regular ones
+- `format` - Format of the file: `html` (the glyphs in a `<pre>`), `svg` (every pixel as a square) or `png` (an image scaled up by `scale`, a labeled sheet for more than one Pokemon)
+- `output` - File to write to. If not specified, the file is written to stdout.
+- `sheet` - Tile every Pokemon into one labeled sheet
+- `shinies` - Put the shiny version of every Pokemon on the sheet as well
+
 ## `fetch` - Fetch the latest colorscripts from the repository
+- `alpha_threshold` - Leave out pixels less opaque than this, from 1 to 255
+- `blend` - Blend partly transparent pixels against this background color, e.g. `#1e1e2e`
 - `extract_destination` - eXtract the colorscripts archive to a custom location
+- `heights` - Also generate the small and big sizes fitted to these numbers of rows, e.g. `8,12`
 - `verbose` - Print colorscripts when generating
 
 ## `list` - Print a list of Pokemon names and Pokedex number
 - `forms` - Print a list of forms of the specified Pokemon
+- `lang` - Language of the names, e.g. `eng`, `jpn`, `jpn_ro` or `chs`
 
 ## `print` - Print a Pokemon colorscript
+- `align` - Position of every row within the terminal width: `left`, `center` or `right`
 - `big` - Print a bigger version of the colorscript
+- `charset` - Characters to draw with: `unicode` or `ascii`
+- `columns` - Maximum number of colorscripts per row. If not specified, rows are fitted to the terminal width.
+- `from-file` - Read Pokemon from a file with one selector per line, or from stdin with `-`
+- `daltonize` - Shift the colors for a kind of color blindness: `protanopia`, `deuteranopia` or `tritanopia`. Also turns on `shiny-indicator`.
+- `filter` - Filter for scaling down to `--height`: `nearest`, `triangle`, `catmull-rom`, `gaussian` or `lanczos3`
+- `flip` - Mirror the colorscripts horizontally
 - `form` - Print Pokemon by list of space-separated forms. Follows the order of the names/Pokedex number specified. If not specified, it will print the regular form.
-- `hide-name` - Do not print Pokemon name
+- `format` - What to print: `ansi` for the terminal, `html` (the glyphs in a `<pre>`) or `svg` (every pixel as a square)
+- `height` - Scale colorscripts down to fit this number of rows
+- `grayscale` - Print the colorscripts in shades of gray
+- `hide-name` - Do not print Pokemon name labels
+- `hue-shift` - Rotate the hues of the colorscripts by a number of degrees
+- `invert` - Invert the colors of the colorscripts
+- `label` - Template for the label of every colorscript, e.g. `{name} #{dex} {form} {shiny:✨}`
+- `label-color` - Color every label with the dominant color of its colorscript
+- `label-position` - Print labels `above` or `below` the colorscripts
+- `lang` - Language of the names in labels, e.g. `eng`, `jpn`, `jpn_ro` or `chs`
+- `light-bg` - Darken colors that are hard to see on a light terminal background, which is asked from the terminal or read from the config file
+- `margin` - Number of blank lines above and below the output, and spaces on its sides
 - `name` - Print Pokemon by list of space-separated names. Use `random` to print a random Pokemon.
+- `outline` - Draw a line in this color around the colorscripts, e.g. `#ffffff`
+- `padding` - Number of spaces on both sides of every colorscript
+- `per-line` - Print every line from `from-file` as its own block
 - `pokedex` - Print Pokemon by list of space-separated Pokedex numbers. Use `0` to print a random Pokemon.
+- `protocol` - Draw the colorscripts as images: `blocks`, `sixel`, `kitty`, `iterm` or `auto` (the best one the terminal supports)
+- `ramp` - Characters from dark to bright for the ascii charset
+- `scale` - Scale the pixels of colorscripts up by a whole number, e.g. `3`
+- `shadow` - Cast a drop shadow down and to the right of the colorscripts
 - `shiny` - Rate of printing the shiny version of the colorscript
+- `shiny-indicator` - Add `[shiny]` to the labels of shiny colorscripts, even with `hide-name`
+- `silhouette` - Paint every pixel of the colorscripts in one color, black if no color is given
+- `size` - Size of the colorscripts: `small`, `big`, `tiny` (2x2 pixels in a cell), `sextant` (2x3), `micro` (2x4 braille) or `auto` (the largest that fits the terminal)
 - `spacing` - Number of spaces between colorscripts
+- `team` - Print a team saved in the config file
+- `valign` - Vertical position of shorter colorscripts in a row: `top`, `center` or `bottom`
 
 ## `say` - Print a speaking Pokemon
+Follows the `[print]` defaults of the config file, and:
+- `form` - Form of the speaking Pokemon
+- `name` - Name of the speaking Pokemon. Use `random` to pick a random Pokemon.
 - `text` - Input text for Pokemon to say
+
+## `team` - Manage teams saved in the config file
+- `add` - Add Pokemon to a team, creating the team if needed
+- `remove` - Remove Pokemon from a team, or the whole team if no Pokemon are given
+- `list` - List saved teams and aliases
 */
 
 /// Pokemon Colorscripts written in Rust
 fn main() {
     let args = rustmon::args::argument_parser();
 
-    if let Some(fetch_args) = args.subcommand_matches("fetch") {
+    if let Some(config_args) = args.subcommand_matches("config") {
+        // config
+        if let Some(show_args) = config_args.subcommand_matches("show") {
+            // config/show
+            if let Err(e) = rustmon::config::show(show_args.get_one::<String>("profile")) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+    } else if let Some(fetch_args) = args.subcommand_matches("fetch") {
         // fetch
-        let extract_destination_raw: &String =
-            fetch_args.get_one::<String>("extract_destination").unwrap();
-        let extract_destination: &std::path::Path = std::path::Path::new(extract_destination_raw);
-        let verbose: bool = fetch_args.get_flag("verbose");
+
+        // merge the command line flags over the config file
+        let settings = match rustmon::config::read_config().and_then(|config| {
+            rustmon::config::resolve_fetch_settings(
+                Some(fetch_args),
+                &config,
+                fetch_args.get_one::<String>("profile"),
+            )
+        }) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        };
+
+        let extract_destination: &std::path::Path =
+            std::path::Path::new(&settings.extract_destination.value);
+        let heights: Vec<u16> = settings.heights.value;
+        let alpha = rustmon::structs::AlphaOptions {
+            threshold: settings.alpha_threshold.value,
+            blend: settings.blend.value,
+        };
+        let verbose: bool = settings.verbose.value;
 
         // display selections
         println!("Extract destination: {}", extract_destination.display());
+        println!("Heights: {heights:?}");
+        println!("Alpha threshold: {}", alpha.threshold);
+        match alpha.blend {
+            Some(color) => println!("Blend: {color}"),
+            None => println!("Blend: none"),
+        }
         println!("Verbose: {verbose}");
 
         // invoke bigchungus fetch function
-        rustmon::fetch::fetch(extract_destination, verbose);
+        rustmon::fetch::fetch(extract_destination, &heights, &alpha, verbose);
     } else if let Some(list_args) = args.subcommand_matches("list") {
         // list
 
@@ -44,9 +137,10 @@ fn main() {
         rustmon::validation::validate_files();
 
         let pokemon_name: &String = list_args.get_one::<String>("forms").unwrap();
+        let lang: &String = list_args.get_one::<String>("lang").unwrap();
         if pokemon_name.is_empty() {
             // list
-            match rustmon::list::print_pokemon_list() {
+            match rustmon::list::print_pokemon_list(lang) {
                 Ok(()) => {}
                 Err(e) => {
                     eprintln!("Error: {e}");
@@ -55,7 +149,7 @@ fn main() {
             };
         } else {
             // list/forms
-            match rustmon::list::print_pokemon_forms(pokemon_name) {
+            match rustmon::list::print_pokemon_forms(pokemon_name, lang) {
                 Ok(_) => {}
                 Err(e) => {
                     eprintln!("Error: {e}");
@@ -63,31 +157,242 @@ fn main() {
                 }
             };
         }
-    } else if let Some(print_args) = args.subcommand_matches("print") {
-        // print
+    } else if let Some(print_args) = args
+        .subcommand_matches("print")
+        .or_else(|| args.subcommand_matches("export"))
+    {
+        // print, or export with the same arguments
 
         // validate files first
         rustmon::validation::validate_files();
 
+        // merge the command line flags over the config file
+        let (settings, aliases) = match rustmon::config::read_config().and_then(|config| {
+            let settings = rustmon::config::resolve_print_settings(
+                Some(print_args),
+                &config,
+                print_args.get_one::<String>("profile"),
+            )?;
+            Ok((settings, config.aliases))
+        }) {
+            Ok(settings_and_aliases) => settings_and_aliases,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        };
+
         // declare and define variables from arguments
-        let big = print_args.get_flag("big");
         let forms: Vec<&String> = print_args.get_many("form").unwrap().collect();
-        let hide_name: bool = print_args.get_flag("hide-name");
-        let names: Vec<&String> = print_args.get_many("name").unwrap().collect();
+        let mut names: Vec<&String> = print_args.get_many("name").unwrap().collect();
         let pokedexes: Vec<u16> = print_args.get_many("pokedex").unwrap().copied().collect();
-        let shiny_rate: f32 = *print_args.get_one::<f32>("shiny").unwrap();
-        let spacing: u8 = *print_args.get_one::<u8>("spacing").unwrap();
+        let export_args = args.subcommand_matches("export");
+        let format = print_args
+            .get_one::<rustmon::structs::Format>("format")
+            .copied()
+            .unwrap_or_default();
+        // markup is not read by a terminal, so it gets every color unless colors are turned off
+        let colors = if format == rustmon::structs::Format::Ansi {
+            rustmon::color::resolve_options(print_args)
+        } else {
+            rustmon::structs::ColorOptions {
+                enabled: print_args.get_one::<rustmon::structs::ColorMode>("color")
+                    != Some(&rustmon::structs::ColorMode::Never),
+                depth: rustmon::structs::ColorDepth::TrueColor,
+                dither: false,
+            }
+        };
+
+        // ask the terminal for its background only when it is needed, and when colors are written at all
+        let background = if print_args.get_flag("light-bg") && colors.enabled {
+            Some(
+                rustmon::color::query_background()
+                    .or(settings.background.value)
+                    .unwrap_or(rustmon::constants::LIGHT_BACKGROUND),
+            )
+        } else {
+            None
+        };
+
+        let options = rustmon::structs::PrintOptions {
+            size: settings.size.value,
+            // only look for graphics support when asked to, and when colors are written at all
+            protocol: match settings.protocol.value {
+                // markup draws the glyphs or pixels itself
+                _ if format != rustmon::structs::Format::Ansi => rustmon::structs::Protocol::Blocks,
+                rustmon::structs::Protocol::Auto if colors.enabled => rustmon::protocol::detect(),
+                rustmon::structs::Protocol::Auto => rustmon::structs::Protocol::Blocks,
+                protocol => protocol,
+            },
+            charset: settings.charset.value,
+            ramp: settings.ramp.value,
+            scale: settings.scale.value,
+            height: settings.height.value,
+            filter: settings.filter.value,
+            effects: rustmon::structs::Effects {
+                flip: print_args.get_flag("flip"),
+                outline: print_args
+                    .get_one::<rustmon::structs::Rgb>("outline")
+                    .copied(),
+                shadow: print_args.get_flag("shadow"),
+                silhouette: print_args
+                    .get_one::<rustmon::structs::Rgb>("silhouette")
+                    .copied(),
+            },
+            palette: rustmon::structs::Palette {
+                hue_shift: print_args
+                    .get_one::<f32>("hue-shift")
+                    .copied()
+                    .unwrap_or(0.0),
+                grayscale: print_args.get_flag("grayscale"),
+                invert: print_args.get_flag("invert"),
+                daltonize: settings.daltonize.value,
+                background,
+            },
+            colors,
+            hide_name: settings.hide_name.value,
+            label: settings.label.value,
+            label_position: settings.label_position.value,
+            label_color: settings.label_color.value,
+            shiny_indicator: settings.shiny_indicator.value || settings.daltonize.value.is_some(),
+            lang: settings.lang.value,
+            shiny_rate: settings.shiny.value,
+            spacing: settings.spacing.value,
+            columns: settings.columns.value,
+            valign: settings.valign.value,
+            align: settings.align.value,
+            margin: settings.margin.value,
+            padding: settings.padding.value,
+            format,
+            // only `export` writes to a file or makes sheets
+            output: export_args
+                .and_then(|export_args| export_args.get_one::<String>("output"))
+                .map(std::path::PathBuf::from),
+            sheet: export_args
+                .filter(|export_args| export_args.get_flag("sheet"))
+                .map(|export_args| rustmon::structs::Sheet {
+                    forms: export_args.get_flag("all-forms"),
+                    shiny: export_args.get_flag("shinies"),
+                }),
+            aliases,
+        };
+
+        // expand the team into names
+        let team_names: Vec<String> = match print_args.get_one::<String>("team") {
+            Some(team) => match rustmon::config::team_tokens(team) {
+                Ok(team_names) => team_names,
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            },
+            None => vec![],
+        };
+        if !team_names.is_empty() {
+            names = team_names.iter().collect();
+        }
+
+        // read names from a file or stdin, one selector per line
+        let selector_lines: Vec<String> = match print_args.get_one::<String>("from-file") {
+            Some(path) => match rustmon::print::read_selector_file(path) {
+                Ok(selector_lines) => selector_lines,
+                Err(e) => {
+                    eprintln!("Error: Failed to read {path}: {e}");
+                    std::process::exit(1);
+                }
+            },
+            None => vec![],
+        };
+        if let (Some(path), true) = (
+            print_args.get_one::<String>("from-file"),
+            selector_lines.is_empty(),
+        ) {
+            eprintln!("Error: No Pokemon found in {path}");
+            std::process::exit(1);
+        }
+
+        if print_args.get_flag("per-line") {
+            // print every line as its own block
+            for selector_line in &selector_lines {
+                let line_names: Vec<String> =
+                    selector_line.split_whitespace().map(String::from).collect();
+                rustmon::print::print(
+                    forms.clone(),
+                    line_names.iter().collect(),
+                    pokedexes.clone(),
+                    &options,
+                );
+            }
+            return;
+        }
+
+        let file_names: Vec<String> = selector_lines
+            .iter()
+            .flat_map(|selector_line| selector_line.split_whitespace().map(String::from))
+            .collect();
+        if !file_names.is_empty() {
+            names = file_names.iter().collect();
+        }
 
         // print
-        rustmon::print::print(big, forms, hide_name, names, pokedexes, shiny_rate, spacing);
+        rustmon::print::print(forms, names, pokedexes, &options);
     } else if let Some(say_args) = args.subcommand_matches("say") {
         // say
 
         // validate files first
         rustmon::validation::validate_files();
 
+        let forms: Vec<&String> = say_args.get_many("form").unwrap().collect();
+        let names: Vec<&String> = say_args.get_many("name").unwrap().collect();
         let text: &String = say_args.get_one::<String>("text").unwrap();
+        let colors = rustmon::color::resolve_options(say_args);
 
-        rustmon::say::say(text);
+        // the speaker looks like printed Pokemon do
+        let config = match rustmon::config::read_config() {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        };
+        let settings = match rustmon::config::resolve_print_settings(
+            None,
+            &config,
+            say_args.get_one::<String>("profile"),
+        ) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        };
+
+        rustmon::say::say(text, names, forms, colors, settings, config.aliases);
+    } else if let Some(team_args) = args.subcommand_matches("team") {
+        // team
+        let result = if let Some(add_args) = team_args.subcommand_matches("add") {
+            // team/add
+            let team: &String = add_args.get_one::<String>("team").unwrap();
+            let selectors: Vec<&String> = add_args.get_many("selectors").unwrap().collect();
+            rustmon::team::add(team, selectors)
+        } else if let Some(remove_args) = team_args.subcommand_matches("remove") {
+            // team/remove
+            let team: &String = remove_args.get_one::<String>("team").unwrap();
+            let selectors: Vec<&String> = remove_args
+                .get_many("selectors")
+                .map(|selectors| selectors.collect())
+                .unwrap_or_default();
+            rustmon::team::remove(team, selectors)
+        } else if let Some(list_args) = team_args.subcommand_matches("list") {
+            // team/list
+            rustmon::team::list(list_args.get_one::<String>("team"))
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = result {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
     }
 }
@@ -0,0 +1,193 @@
+/// Turn printed lines into an html document, every run of glyphs with the same colors becomes a `<span>`
+/// the glyphs are kept, so half blocks still show two pixels in a cell
+pub fn html(lines: &[String]) -> String {
+    let mut body = String::new();
+
+    for line in lines {
+        for (foreground, background, text) in spans(line) {
+            let mut style: Vec<String> = vec![];
+            if let Some((r, g, b)) = foreground {
+                style.push(format!("color:{}", crate::structs::Rgb(r, g, b)));
+            }
+            if let Some((r, g, b)) = background {
+                style.push(format!("background-color:{}", crate::structs::Rgb(r, g, b)));
+            }
+
+            if style.is_empty() {
+                body.push_str(&escape(&text));
+            } else {
+                body.push_str(&format!(
+                    "<span style=\"{}\">{}</span>",
+                    style.join(";"),
+                    escape(&text)
+                ));
+            }
+        }
+        body.push('\n');
+    }
+
+    // a line height of one makes the half blocks of neighbouring lines touch
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>rustmon</title>
+<style>pre {{ font-family: monospace; line-height: 1; }}</style>
+</head>
+<body>
+<pre>
+{}</pre>
+</body>
+</html>
+",
+        body
+    )
+}
+
+/// Draw rows of pixel grids and their labels as an svg document, every pixel as a square
+/// spacing, padding and margins follow the print options, counting a column as a pixel and a line as two
+pub fn svg(
+    rows: &[Vec<(crate::structs::PixelGrid, String)>],
+    options: &crate::structs::PrintOptions,
+) -> String {
+    let font_size = crate::constants::SVG_FONT_SIZE;
+    // monospace glyphs are about three fifths as wide as they are high
+    let label_width =
+        |label: &str| (unicode_width::UnicodeWidthStr::width(label) * font_size * 3).div_ceil(5);
+    let padding = options.padding as usize;
+    let spacing = options.spacing as usize;
+    let margin = options.margin as usize;
+
+    let mut elements = String::new();
+    let mut width = 0;
+    let mut y = 2 * margin;
+
+    for row in rows {
+        // labels are colored by their escape codes, if they have any
+        let labels: Vec<_> = row
+            .iter()
+            .map(|(_, label)| {
+                let spans = spans(label);
+                let color = spans.first().and_then(|(foreground, _, _)| *foreground);
+                let text: String = spans.into_iter().map(|(_, _, text)| text).collect();
+                (text, color)
+            })
+            .collect();
+        let labels_height = if labels.iter().any(|(text, _)| !text.is_empty()) {
+            font_size + 2
+        } else {
+            0
+        };
+        let max_height = row.iter().map(|(grid, _)| grid.height).max().unwrap_or(0);
+
+        let (labels_y, artwork_y) = match options.label_position {
+            crate::structs::LabelPosition::Above => (y, y + labels_height),
+            crate::structs::LabelPosition::Below => (y + max_height, y),
+        };
+
+        let mut x = margin;
+        for ((grid, _), (label, color)) in row.iter().zip(&labels) {
+            let cell_width = grid.width.max(label_width(label));
+
+            // shift shorter artworks down depending on the vertical alignment
+            let top = options.valign.offset(grid.height, max_height);
+            push_pixels(
+                &mut elements,
+                grid,
+                x + padding + (cell_width - grid.width) / 2,
+                artwork_y + top,
+            );
+
+            if !label.is_empty() {
+                let fill = match color {
+                    Some((r, g, b)) => format!(" fill=\"{}\"", crate::structs::Rgb(*r, *g, *b)),
+                    None => String::new(),
+                };
+                elements.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\"{}>{}</text>\n",
+                    x + padding + cell_width / 2,
+                    labels_y + font_size,
+                    fill,
+                    escape(label)
+                ));
+            }
+
+            x += cell_width + 2 * padding + spacing;
+        }
+
+        // no spacing after the last colorscript
+        width = width.max((x + margin).saturating_sub(spacing));
+        // a blank line between rows, like in the terminal
+        y += labels_height + max_height + 2;
+    }
+
+    let height = y + 2 * margin;
+    let pixel_size = crate::constants::SVG_PIXEL_SIZE;
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\" font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\">
+{}</svg>
+",
+        width * pixel_size,
+        height * pixel_size,
+        width,
+        height,
+        font_size,
+        elements
+    )
+}
+
+/// Push the opaque pixels of a grid as rectangles, pixels of the same color next to each other share one
+fn push_pixels(elements: &mut String, grid: &crate::structs::PixelGrid, left: usize, top: usize) {
+    for y in 0..grid.height {
+        let mut x = 0;
+        while x < grid.width {
+            let pixel = grid.get(x, y);
+            let run = (x..grid.width)
+                .take_while(|end| grid.get(*end, y) == pixel)
+                .count();
+
+            if pixel[3] != 0 {
+                elements.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                    left + x,
+                    top + y,
+                    run,
+                    crate::structs::Rgb(pixel[0], pixel[1], pixel[2])
+                ));
+            }
+
+            x += run;
+        }
+    }
+}
+
+// foreground color, background color and the text in them
+type Span = (Option<(u8, u8, u8)>, Option<(u8, u8, u8)>, String);
+
+/// Split a printed line into runs of text with the same foreground and background colors
+fn spans(line: &str) -> Vec<Span> {
+    let mut spans: Vec<Span> = vec![];
+
+    for cell in crate::colorscript::parse_cells(line) {
+        match spans.last_mut() {
+            Some((foreground, background, text))
+                if *foreground == cell.foreground && *background == cell.background =>
+            {
+                text.push(cell.glyph)
+            }
+            _ => spans.push((cell.foreground, cell.background, cell.glyph.to_string())),
+        }
+    }
+
+    spans
+}
+
+/// Escape the characters that mean something in html and svg
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,88 @@
+/// Re-encode colorscripts written by older versions of `fetch`, once per install
+/// older versions wrote the full color codes and a reset for every cell
+pub fn migrate_colorscripts() -> Result<(), Box<dyn std::error::Error>> {
+    let colorscripts_path = crate::constants::DATA_DIRECTORY.join("colorscripts");
+
+    // installs without a version are from before the versions were written down
+    let version: u8 = std::fs::read_to_string(colorscripts_path.join(".encoding"))
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+        .unwrap_or(1);
+    if version >= crate::constants::COLORSCRIPT_ENCODING_VERSION {
+        return Ok(());
+    }
+
+    eprintln!("Re-encoding colorscripts in a more compact format, this only happens once...");
+
+    for size in [crate::structs::Size::Small, crate::structs::Size::Big] {
+        for subdirectory in ["regular", "shiny"].iter() {
+            let directory_path = colorscripts_path.join(size.to_string()).join(subdirectory);
+
+            for entry in std::fs::read_dir(directory_path)? {
+                let path = entry?.path();
+                if !path.is_file() {
+                    continue;
+                }
+
+                let colorscript = std::fs::read_to_string(&path)?;
+                std::fs::write(&path, re_encode(&colorscript, size))?;
+            }
+        }
+    }
+
+    write_encoding_version(&colorscripts_path)?;
+
+    eprintln!("Re-encoded colorscripts");
+
+    Ok(())
+}
+
+/// Write a colorscript again in the current encoding, it draws the same pixels
+fn re_encode(colorscript: &str, size: crate::structs::Size) -> String {
+    crate::colorscript::write(&crate::colorscript::parse(colorscript, size), size)
+}
+
+/// Write down the encoding of the colorscripts in a directory, so that they are not migrated again
+pub fn write_encoding_version(colorscripts_path: &std::path::Path) -> std::io::Result<()> {
+    std::fs::write(
+        colorscripts_path.join(".encoding"),
+        crate::constants::COLORSCRIPT_ENCODING_VERSION.to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::Size;
+
+    #[test]
+    fn re_encodes_a_reset_for_every_cell() {
+        // older versions wrote the full colors and a reset for every cell
+        let cases = [
+            (
+                Size::Small,
+                "\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m▀\x1b[0m\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m▀\x1b[0m \x1b[38;2;0;0;255m▄\x1b[0m\n",
+                "\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m▀▀\x1b[0m \x1b[38;2;0;0;255m▄\x1b[0m\n",
+            ),
+            (
+                Size::Big,
+                "\x1b[38;2;255;0;0m██\x1b[0m\x1b[38;2;255;0;0m██\x1b[0m  \x1b[38;2;0;0;255m██\x1b[0m\n",
+                "\x1b[38;2;255;0;0m████  \x1b[38;2;0;0;255m██\x1b[0m\n",
+            ),
+        ];
+
+        for (size, old, expected) in cases {
+            let new = super::re_encode(old, size);
+
+            assert_eq!(new, expected, "{size}");
+            // the same pixels and the same glyphs without colors
+            assert_eq!(
+                crate::colorscript::parse(&new, size),
+                crate::colorscript::parse(old, size),
+                "{size}"
+            );
+            for (new_line, old_line) in new.lines().zip(old.lines()) {
+                assert_eq!(crate::color::strip(new_line), crate::color::strip(old_line));
+            }
+        }
+    }
+}
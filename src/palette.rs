@@ -0,0 +1,262 @@
+/// Apply the palette transforms of the print options to every pixel of a grid
+/// the hue is shifted first, then the colors are grayed, inverted and daltonized, and finally pushed away from the background
+pub fn apply(
+    grid: &crate::structs::PixelGrid,
+    palette: &crate::structs::Palette,
+) -> crate::structs::PixelGrid {
+    let mut transformed_grid = grid.clone();
+
+    for pixel in transformed_grid.pixels.iter_mut() {
+        if pixel[3] == 0 {
+            continue;
+        }
+
+        let mut color = (pixel[0], pixel[1], pixel[2]);
+        if palette.hue_shift != 0.0 {
+            color = hue_shift(color, palette.hue_shift);
+        }
+        if palette.grayscale {
+            color = grayscale(color);
+        }
+        if palette.invert {
+            color = invert(color);
+        }
+        if let Some(deficiency) = palette.daltonize {
+            color = daltonize(color, deficiency);
+        }
+        if let Some(background) = palette.background {
+            color = contrast(color, background);
+        }
+
+        *pixel = [color.0, color.1, color.2, pixel[3]];
+    }
+
+    transformed_grid
+}
+
+/// Rotate the hue of a color by a number of degrees, keeping its saturation and lightness
+pub fn hue_shift(color: (u8, u8, u8), degrees: f32) -> (u8, u8, u8) {
+    let (hue, saturation, lightness) = to_hsl(color);
+    from_hsl((hue + degrees).rem_euclid(360.0), saturation, lightness)
+}
+
+/// Gray of the same brightness as a color
+pub fn grayscale(color: (u8, u8, u8)) -> (u8, u8, u8) {
+    let gray = (luminance(color) * 255.0).round() as u8;
+    (gray, gray, gray)
+}
+
+/// Opposite of a color
+pub fn invert(color: (u8, u8, u8)) -> (u8, u8, u8) {
+    (255 - color.0, 255 - color.1, 255 - color.2)
+}
+
+/// Shift the colors a kind of color blindness cannot tell apart into ones it can
+/// simulates the deficiency in LMS space, and moves the lost difference onto the channels that are still seen
+/// see http://www.daltonize.org
+pub fn daltonize(color: (u8, u8, u8), deficiency: crate::structs::ColorDeficiency) -> (u8, u8, u8) {
+    let rgb = [color.0 as f32, color.1 as f32, color.2 as f32];
+    let [l, m, s] = multiply(&RGB_TO_LMS, rgb);
+
+    // the cone that is missing is guessed from the other two
+    let simulated_lms = match deficiency {
+        crate::structs::ColorDeficiency::Protanopia => [2.02344 * m - 2.52581 * s, m, s],
+        crate::structs::ColorDeficiency::Deuteranopia => [l, 0.494207 * l + 1.24827 * s, s],
+        crate::structs::ColorDeficiency::Tritanopia => [l, m, -0.395913 * l + 0.801109 * m],
+    };
+    let simulated_rgb = multiply(&LMS_TO_RGB, simulated_lms);
+
+    let error = [
+        rgb[0] - simulated_rgb[0],
+        rgb[1] - simulated_rgb[1],
+        rgb[2] - simulated_rgb[2],
+    ];
+    let error_shift = match deficiency {
+        crate::structs::ColorDeficiency::Protanopia
+        | crate::structs::ColorDeficiency::Deuteranopia => &RED_GREEN_ERROR_SHIFT,
+        crate::structs::ColorDeficiency::Tritanopia => &BLUE_YELLOW_ERROR_SHIFT,
+    };
+    let shift = multiply(error_shift, error);
+    let channel = |i: usize| (rgb[i] + shift[i]).round().clamp(0.0, 255.0) as u8;
+
+    (channel(0), channel(1), channel(2))
+}
+
+// matrices from Fidaner, Lin and Ozguven, the ones used by most daltonize implementations
+const RGB_TO_LMS: [[f32; 3]; 3] = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.0299566, 0.184309, 1.46709],
+];
+const LMS_TO_RGB: [[f32; 3]; 3] = [
+    [0.08094445, -0.13050441, 0.11672107],
+    [-0.010248533, 0.05401933, -0.11361471],
+    [-0.00036529694, -0.004121615, 0.6935114],
+];
+// without red or green cones the difference in red is moved to green and blue
+const RED_GREEN_ERROR_SHIFT: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]];
+// without blue cones the difference in blue is moved to red and green
+const BLUE_YELLOW_ERROR_SHIFT: [[f32; 3]; 3] = [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]];
+
+fn multiply(matrix: &[[f32; 3]; 3], vector: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+/// Darken or brighten a color that is too close in brightness to the background
+/// colors on a light background are darkened, colors on a dark background are brightened
+pub fn contrast(color: (u8, u8, u8), background: crate::structs::Rgb) -> (u8, u8, u8) {
+    let brightness = luminance(color);
+    let background_brightness = luminance((background.0, background.1, background.2));
+
+    if (brightness - background_brightness).abs() >= crate::constants::MIN_CONTRAST {
+        return color;
+    }
+
+    let scale = |value: u8, factor: f32| (value as f32 * factor).round().clamp(0.0, 255.0) as u8;
+    if background_brightness > 0.5 {
+        // scale towards black
+        let target = (background_brightness - crate::constants::MIN_CONTRAST).max(0.0);
+        let factor = if brightness > 0.0 {
+            target / brightness
+        } else {
+            0.0
+        };
+        (
+            scale(color.0, factor),
+            scale(color.1, factor),
+            scale(color.2, factor),
+        )
+    } else {
+        // mix towards white
+        let target = (background_brightness + crate::constants::MIN_CONTRAST).min(1.0);
+        let mix = (target - brightness) / (1.0 - brightness).max(f32::EPSILON);
+        let brighten = |value: u8| (value as f32 + (255.0 - value as f32) * mix).round() as u8;
+        (brighten(color.0), brighten(color.1), brighten(color.2))
+    }
+}
+
+/// Perceived brightness of a color, from 0 to 1
+fn luminance(color: (u8, u8, u8)) -> f32 {
+    (0.2126 * color.0 as f32 + 0.7152 * color.1 as f32 + 0.0722 * color.2 as f32) / 255.0
+}
+
+/// Hue in degrees, saturation and lightness from 0 to 1
+fn to_hsl(color: (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        color.0 as f32 / 255.0,
+        color.1 as f32 / 255.0,
+        color.2 as f32 / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    // grays have no hue
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, saturation, lightness)
+}
+
+fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |value: f32| ((value + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    (channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::ColorDeficiency;
+
+    const COLORS: [(u8, u8, u8); 6] = [
+        (0, 0, 0),
+        (255, 255, 255),
+        (255, 0, 0),
+        (30, 144, 255),
+        (128, 64, 200),
+        (246, 213, 49),
+    ];
+
+    #[test]
+    fn hue_shift_by_a_full_turn_keeps_the_color() {
+        for color in COLORS {
+            assert_eq!(super::hue_shift(color, 360.0), color, "{color:?}");
+            assert_eq!(super::hue_shift(color, -360.0), color, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn grayscale_keeps_the_brightness() {
+        let cases = [
+            ((0, 0, 0), (0, 0, 0)),
+            ((255, 255, 255), (255, 255, 255)),
+            ((255, 0, 0), (54, 54, 54)),
+            ((0, 255, 0), (182, 182, 182)),
+            ((0, 0, 255), (18, 18, 18)),
+        ];
+
+        for (color, gray) in cases {
+            assert_eq!(super::grayscale(color), gray, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn invert_twice_keeps_the_color() {
+        assert_eq!(super::invert((30, 144, 255)), (225, 111, 0));
+        for color in COLORS {
+            assert_eq!(super::invert(super::invert(color)), color, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn daltonize_moves_the_lost_difference() {
+        let cases = [
+            // red and green cannot be told apart, so red gains green and blue
+            (ColorDeficiency::Protanopia, (255, 0, 0), (255, 130, 157)),
+            (ColorDeficiency::Deuteranopia, (255, 0, 0), (255, 52, 132)),
+            // blue and yellow cannot be told apart, so the difference goes to red and green
+            (ColorDeficiency::Tritanopia, (30, 144, 255), (0, 0, 255)),
+        ];
+
+        for (deficiency, color, daltonized) in cases {
+            assert_eq!(
+                super::daltonize(color, deficiency),
+                daltonized,
+                "{deficiency:?}"
+            );
+        }
+        // grays are seen the same by everyone
+        for deficiency in [
+            ColorDeficiency::Protanopia,
+            ColorDeficiency::Deuteranopia,
+            ColorDeficiency::Tritanopia,
+        ] {
+            for gray in [(0, 0, 0), (255, 255, 255)] {
+                assert_eq!(super::daltonize(gray, deficiency), gray, "{deficiency:?}");
+            }
+        }
+    }
+}
@@ -1,68 +1,285 @@
 use rand::prelude::SliceRandom;
 use rand::Rng;
 use rand::SeedableRng;
-use std::io::BufRead;
 use std::io::Read;
+use std::io::Write;
 
 pub fn print(
-    big: bool,
     forms: Vec<&String>,
-    hide_name: bool,
     names: Vec<&String>,
     pokedexes: Vec<u16>,
-    shiny_rate: f32,
-    spacing: u8,
+    options: &crate::structs::PrintOptions,
 ) {
     // decide which function to call
-    if !big
+    if options.size == crate::structs::Size::Small
+        && options.charset == crate::structs::Charset::Unicode
+        && options.protocol == crate::structs::Protocol::Blocks
+        && options.format == crate::structs::Format::Ansi
+        && options.scale == 1
+        && options.height.is_none()
+        && !options.effects.any()
+        && !options.palette.any()
         // uber fast random
         && forms.len() == 1
-        && !hide_name
+        // labels need the proper name from `pokemon.json`, so only take the shortcut without one
+        && options.hide_name
+        // the files are printed as is, escape codes and all
+        && options.colors.enabled
+        && options.colors.depth == crate::structs::ColorDepth::TrueColor
         && (names.len() == 1 && pokedexes.len() == 1)
-        && shiny_rate == 0.0
+        && options.shiny_rate == 0.0
         && forms[0] == "regular"
         && (names[0] == "random" && pokedexes[0] == 0)
     {
         random_lite().unwrap();
     } else {
-        // convert list of names to list of pokedex numbers
-        let pokedexes = if names[0].is_empty() {
-            pokedexes.clone()
+        // every Pokemon for a sheet, or the ones asked for
+        let slugs = match options.sheet {
+            Some(sheet) => match sheet_sprites(sheet, options) {
+                Ok(slugs) => slugs,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            },
+            None => select_sprites(forms, names, pokedexes, options),
+        };
+
+        // png has no glyphs, only pixels
+        if options.format == crate::structs::Format::Png {
+            if let Err(e) = write_png(&slugs, options) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+
+        // `--size auto` picks the size that fits the terminal
+        let (slugs, options) = if options.size == crate::structs::Size::Auto {
+            match fit_size(&slugs, options) {
+                Ok(fitted) => fitted,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
         } else {
-            let mut pokedexes: Vec<u16> = Vec::new();
-            for name in names {
-                match find_pokedex_by_pokemon(name) {
-                    Ok(pokedex) => pokedexes.push(pokedex.parse().unwrap()),
-                    Err(e) => {
-                        println!("Error: {}", e);
-                        std::process::exit(1);
+            (slugs, options.clone())
+        };
+        let options = &options;
+
+        // read the colorscripts and split them into rows that fit the terminal
+        let colorscripts = match read_colorscripts(&slugs, options) {
+            Ok(colorscripts) => colorscripts,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let rows = split_into_rows(&colorscripts, options);
+
+        match options.format {
+            crate::structs::Format::Ansi => {
+                // top margin
+                print!("{}", "\n".repeat(options.margin as usize));
+
+                for row in rows {
+                    // every row is aligned on its own
+                    let offset = row_offset(&colorscripts[row.clone()], options);
+
+                    // print the actual thing
+                    for line in row_lines(&colorscripts[row], options, offset) {
+                        println!("{}", line);
                     }
                 }
+
+                // bottom margin
+                print!("{}", "\n".repeat(options.margin as usize));
+            }
+            // html and svg, png is written above
+            _ => {
+                if let Err(e) = write_markup(&slugs, &colorscripts, rows, options) {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Sprites picked by the names, pokedex numbers and forms given to `print`
+fn select_sprites(
+    forms: Vec<&String>,
+    names: Vec<&String>,
+    pokedexes: Vec<u16>,
+    options: &crate::structs::PrintOptions,
+) -> Vec<crate::structs::Sprite> {
+    // forms specified with `--form`, natural language forms are filled in below
+    let mut forms: Vec<String> = forms.iter().map(|s| s.to_string()).collect();
+
+    // forced shinies from `:shiny` selectors
+    let mut shinies: Vec<bool> = Vec::new();
+
+    // convert list of names to list of pokedex numbers
+    let pokedexes = if names[0].is_empty() {
+        pokedexes.clone()
+    } else {
+        // understand names such as `alolan raichu` or `mega charizard x`
+        let selections = match crate::forms::parse_names(&names, &options.aliases) {
+            Ok(selections) => selections,
+            Err(e) => {
+                println!("Error: {}", e);
+                std::process::exit(1);
             }
-            pokedexes
         };
 
-        // process the pokedexes list
-        // iterate through the pokedexes list, if value is 0, then generate a random number between 1 and 905
-        // if the value is not 0, then use the value as is
-        let pokedexes = process_pokedexes_list(pokedexes);
+        let mut pokedexes: Vec<u16> = Vec::new();
+        for (i, selection) in selections.iter().enumerate() {
+            match find_pokedex_by_pokemon(&selection.name) {
+                Ok(pokedex) => pokedexes.push(pokedex.parse().unwrap()),
+                Err(e) => {
+                    println!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+
+            // a form in the name takes priority over the one in `--form`
+            if let Some(form) = &selection.form {
+                if forms.len() <= i {
+                    forms.resize_with(i + 1, || "regular".to_string());
+                }
+                forms[i] = form.clone();
+            }
+
+            shinies.push(selection.shiny);
+        }
+        pokedexes
+    };
 
-        // process the forms list
-        // the length of the forms list should be the same as the pokedexes list, resize with `regular` if different length
-        // if the form is not available for the pokemon then print the available forms and exit
-        let forms = process_forms_list(&pokedexes, forms);
+    // process the pokedexes list
+    // iterate through the pokedexes list, if value is 0, then generate a random number between 1 and 905
+    // if the value is not 0, then use the value as is
+    let pokedexes = process_pokedexes_list(pokedexes);
 
-        // generate a list of slugs
-        let slugs = generate_slug_list(big, forms, &pokedexes, shiny_rate);
+    // process the forms list
+    // the length of the forms list should be the same as the pokedexes list, resize with `regular` if different length
+    // if the form is not available for the pokemon then print the available forms and exit
+    let forms = process_forms_list(&pokedexes, forms);
 
-        // if hide_name is false then print the names of the slugs, separated by comma
-        if !hide_name {
-            print_name(&slugs);
+    // generate a list of slugs
+    generate_slug_list(forms, &pokedexes, &shinies, options)
+}
+
+/// Write rows of colorscripts as an html or svg document, to the output file of the print options or to stdout
+fn write_markup(
+    sprites: &[crate::structs::Sprite],
+    colorscripts: &[Colorscript],
+    rows: Vec<std::ops::Range<usize>>,
+    options: &crate::structs::PrintOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let markup = if options.format == crate::structs::Format::Html {
+        // there is no terminal to align against, so every row starts after the margin
+        let margin = options.margin as usize;
+        let mut lines: Vec<String> = vec![String::new(); margin];
+        for row in rows {
+            lines.extend(row_lines(&colorscripts[row], options, margin));
+        }
+        lines.extend(vec![String::new(); margin]);
+
+        crate::markup::html(&lines)
+    } else {
+        // svg draws the pixels themselves, the colorscripts only give their labels
+        let mut grid_rows: Vec<Vec<(crate::structs::PixelGrid, String)>> = vec![];
+        for row in rows {
+            let mut grids = vec![];
+            for i in row {
+                grids.push((
+                    read_grid(&sprites[i].path, options)?,
+                    colorscripts[i].label.clone(),
+                ));
+            }
+            grid_rows.push(grids);
         }
 
-        // print the actual thing
-        print_colorscripts(&slugs, spacing).unwrap();
+        crate::markup::svg(&grid_rows, options)
+    };
+
+    match &options.output {
+        Some(path) => std::fs::write(path, markup)?,
+        None => print!("{}", markup),
+    }
+
+    Ok(())
+}
+
+/// Write the pixels of the colorscripts as a png, to the output file of the print options or to stdout
+/// one colorscript is written as it is, more are tiled into a labeled sheet
+fn write_png(
+    sprites: &[crate::structs::Sprite],
+    options: &crate::structs::PrintOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let grid = if let [sprite] = sprites {
+        read_grid(&sprite.path, options)?
+    } else {
+        // the labels are drawn a pixel per dot, so the whole sheet is scaled up at the end instead of every colorscript
+        let unscaled_options = crate::structs::PrintOptions {
+            scale: 1,
+            ..options.clone()
+        };
+        let mut cells: Vec<(crate::structs::PixelGrid, String)> = vec![];
+        for sprite in sprites {
+            cells.push((
+                read_grid(&sprite.path, &unscaled_options)?,
+                render_label(sprite, options),
+            ));
+        }
+
+        crate::resize::upscale(&crate::sheet::tile(&cells, options), options.scale)
+    };
+
+    let png = crate::colorscript::to_png(&grid)?;
+    match &options.output {
+        Some(path) => std::fs::write(path, png)?,
+        None => std::io::stdout().write_all(&png)?,
+    }
+
+    Ok(())
+}
+
+/// Read selectors from a file, or from stdin if the path is `-`
+/// returns one entry per non-empty line, `#` starts a comment
+/// lines from `rustmon list` such as `[25] pikachu` are understood as well
+pub fn read_selector_file(path: &str) -> std::io::Result<Vec<String>> {
+    let mut contents = String::new();
+    if path == "-" {
+        std::io::stdin().read_to_string(&mut contents)?;
+    } else {
+        std::fs::File::open(path)?.read_to_string(&mut contents)?;
     }
+
+    let lines = contents
+        .lines()
+        // strip comments
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        // skip hints from `rustmon list`
+        .filter(|line| !line.is_empty() && !line.starts_with("Hint:"))
+        // strip the pokedex number from `rustmon list` output
+        .map(
+            |line| match line.strip_prefix('[').and_then(|l| l.split_once(']')) {
+                Some((_, name)) => name.trim(),
+                None => line,
+            },
+        )
+        // prefer the slug from `rustmon list` output, e.g. `mr-mime` in `Mr. Mime (mr-mime)`
+        .map(
+            |line| match line.strip_suffix(')').and_then(|l| l.rsplit_once(" (")) {
+                Some((_, slug)) => slug.to_string(),
+                None => line.to_string(),
+            },
+        )
+        .collect();
+
+    Ok(lines)
 }
 
 fn random_lite() -> std::io::Result<()> {
@@ -76,13 +293,6 @@ fn random_lite() -> std::io::Result<()> {
 
     let mut rng = rand::rngs::SmallRng::from_entropy();
     if let Some(random_file) = files.choose(&mut rng) {
-        if let Some(file_name) = random_file.file_name() {
-            match file_name.to_str() {
-                Some(name) => println!("{}", name),
-                None => println!("Invalid UTF-8 sequence in file name"),
-            }
-        }
-
         match std::fs::read_to_string(random_file) {
             Ok(file_data) => {
                 println!("{}", file_data);
@@ -136,15 +346,31 @@ fn find_pokedex_by_pokemon(pokemon_name: &str) -> Result<String, Box<dyn std::er
         let pokemons: Vec<crate::structs::Pokemon> = serde_json::from_str(&contents)?;
 
         // iterate through the list to find the specified pokemon
-        for pokemon in pokemons {
-            if pokemon.name == pokemon_name {
-                // if found then return the pokedex number
-                return Ok(pokemon.pokedex);
-            }
+        // case and punctuation insensitive, so `Mr. Mime` finds `mr-mime`
+        let query = crate::search::normalize(pokemon_name);
+        if let Some(pokemon) = pokemons.iter().find(|pokemon| {
+            pokemon
+                .names()
+                .iter()
+                .any(|name| crate::search::normalize(name) == query)
+        }) {
+            // if found then return the pokedex number
+            return Ok(pokemon.pokedex.clone());
         }
 
-        // if not found the return an error
-        Err(format!("Pokemon {} not found", pokemon_name).into())
+        // if not found the return an error with the closest names
+        let suggestions = crate::search::suggest(
+            pokemon_name,
+            pokemons.iter().map(|pokemon| pokemon.name.as_str()),
+        );
+        let mut message = format!("Pokemon {} not found", pokemon_name);
+        if !suggestions.is_empty() {
+            message.push_str(&format!(
+                "\n{}",
+                crate::search::format_suggestions(&suggestions)
+            ));
+        }
+        Err(message.into())
     }
 }
 
@@ -169,42 +395,53 @@ fn process_pokedexes_list(pokedexes: Vec<u16>) -> Vec<u16> {
     pokedexes_processed
 }
 
-fn process_forms_list(pokedexes: &[u16], forms: Vec<&String>) -> Vec<String> {
-    let mut forms_processed: Vec<String> = forms.iter().map(|s| s.to_string()).collect();
+fn process_forms_list(pokedexes: &[u16], forms: Vec<String>) -> Vec<String> {
+    let mut forms_processed: Vec<String> = forms;
 
     // ensure forms_processed has the same length as pokedexes
     forms_processed.resize_with(pokedexes.len(), || "regular".to_string());
 
     for i in 0..pokedexes.len() {
         let pokemon = get_pokemon_data(pokedexes[i]);
-        let form = &forms_processed[i];
-
-        if !pokemon.forms.contains(&form.to_string()) {
-            // iterate and print out the available forms
-            // consider using crate::list::print_pokemon_forms(pokemon_name)
-            println!("Available forms for {}:", pokemon.name);
-            for available_form in &pokemon.forms {
-                println!(" - {available_form}");
+        let form = forms_processed[i].clone();
+
+        // case and punctuation insensitive, so `Mega X` finds `mega-x`
+        match crate::search::find_match(&form, pokemon.forms.iter().map(|f| f.as_str())) {
+            Some(matched_form) => forms_processed[i] = matched_form.to_string(),
+            None => {
+                println!("Form {} not found for {}", form, pokemon.get_display_name());
+
+                // offer the closest forms first
+                let suggestions =
+                    crate::search::suggest(&form, pokemon.forms.iter().map(|f| f.as_str()));
+                if !suggestions.is_empty() {
+                    println!("{}", crate::search::format_suggestions(&suggestions));
+                }
+
+                // iterate and print out the available forms
+                // consider using crate::list::print_pokemon_forms(pokemon_name)
+                println!("Available forms for {}:", pokemon.get_display_name());
+                for available_form in &pokemon.forms {
+                    println!(" - {available_form}");
+                }
+                std::process::exit(1);
             }
-            std::process::exit(1);
         }
     }
 
     forms_processed
 }
 
-fn slug_generator(big: bool, form: String, name: String, shiny_rate: f32) -> std::path::PathBuf {
-    // big is just a boolean, convert it to big or small
+fn slug_generator(
+    size: crate::structs::Size,
+    form: String,
+    name: String,
+    shiny: bool,
+) -> std::path::PathBuf {
+    // size is the directory of the colorscripts
     // form is a string, if `regular` then replace with empty string. else keep it as is.
     // name is a string, should be cleaned up already. there should be no `random` as a name should be generated before this.
-    // shiny_rate is a float, let it be
-
-    // if big is true then use big, else use small
-    let big: String = if big {
-        "big".to_string()
-    } else {
-        "small".to_string()
-    };
+    // shiny is already decided by the caller
 
     // if form is regular then replace with empty string
     let form: String = if form == "regular" {
@@ -214,7 +451,7 @@ fn slug_generator(big: bool, form: String, name: String, shiny_rate: f32) -> std
     };
 
     // determine if shiny directory is to be used
-    let shiny_directory: String = if is_shiny(shiny_rate) {
+    let shiny_directory: String = if shiny {
         "shiny".to_string()
     } else {
         "regular".to_string()
@@ -223,7 +460,7 @@ fn slug_generator(big: bool, form: String, name: String, shiny_rate: f32) -> std
     // construct the path using PathBuf
     let mut path = std::path::PathBuf::new();
     path.push(crate::constants::DATA_DIRECTORY.join("colorscripts"));
-    path.push(&big);
+    path.push(size.to_string());
     path.push(shiny_directory);
     path.push(format!("{name}{form}"));
 
@@ -231,104 +468,697 @@ fn slug_generator(big: bool, form: String, name: String, shiny_rate: f32) -> std
 }
 
 fn generate_slug_list(
-    big: bool,
     forms: Vec<String>,
     pokedexes: &[u16],
-    shiny_rate: f32,
-) -> Vec<std::path::PathBuf> {
-    let mut slugs: Vec<std::path::PathBuf> = Vec::new();
+    shinies: &[bool],
+    options: &crate::structs::PrintOptions,
+) -> Vec<crate::structs::Sprite> {
+    let mut slugs: Vec<crate::structs::Sprite> = Vec::new();
 
     // iterate through the pokedexes to generate the slugs with the complementing form
     for i in 0..pokedexes.len() {
         let pokemon = get_pokemon_data(pokedexes[i]);
         let form = &forms[i];
 
-        let slug = slug_generator(big, form.to_string(), pokemon.name, shiny_rate);
-        slugs.push(slug);
+        // forced shinies always win, the rest roll against the shiny rate
+        let shiny = shinies.get(i).copied().unwrap_or(false) || is_shiny(options.shiny_rate);
+
+        let slug = slug_generator(options.size, form.to_string(), pokemon.name.clone(), shiny);
+        slugs.push(crate::structs::Sprite {
+            path: slug,
+            name: pokemon.get_name(&options.lang).to_string(),
+            pokedex: pokedexes[i],
+            form: form.to_string(),
+            shiny,
+        });
     }
 
     slugs
 }
 
-fn print_name(paths: &[std::path::PathBuf]) {
-    let last_parts: Vec<&str> = paths
-        .iter()
-        .filter_map(|path| path.file_name())
-        .filter_map(|os_str| os_str.to_str())
-        .collect();
+/// Every Pokemon for `export --sheet`, in pokedex order
+/// every form and the shiny versions follow their Pokemon if the sheet asks for them
+fn sheet_sprites(
+    sheet: crate::structs::Sheet,
+    options: &crate::structs::PrintOptions,
+) -> Result<Vec<crate::structs::Sprite>, Box<dyn std::error::Error>> {
+    // read the file once, instead of once for every Pokemon
+    let contents = std::fs::read_to_string(crate::constants::DATA_DIRECTORY.join("pokemon.json"))?;
+    let pokemons: Vec<crate::structs::Pokemon> = serde_json::from_str(&contents)?;
+
+    let shinies: &[bool] = if sheet.shiny {
+        &[false, true]
+    } else {
+        &[false]
+    };
+    let mut sprites: Vec<crate::structs::Sprite> = vec![];
+
+    for pokemon in &pokemons {
+        let forms: Vec<String> = if sheet.forms {
+            pokemon.forms.clone()
+        } else {
+            vec!["regular".to_string()]
+        };
+
+        for form in forms {
+            for shiny in shinies {
+                let path = slug_generator(options.size, form.clone(), pokemon.name.clone(), *shiny);
 
-    let output = last_parts.join(", ");
-    println!("{}", output);
+                // some forms in `pokemon.json` have no sprite
+                if !size_path(&path, &crate::structs::Size::Small.to_string())?.exists() {
+                    continue;
+                }
+
+                sprites.push(crate::structs::Sprite {
+                    path,
+                    name: pokemon.get_name(&options.lang).to_string(),
+                    pokedex: pokemon.pokedex.parse()?,
+                    form: form.clone(),
+                    shiny: *shiny,
+                });
+            }
+        }
+    }
+
+    Ok(sprites)
+}
+
+/// A colorscript read from disk
+struct Colorscript {
+    lines: Vec<String>,
+    // width of every line with the ansi escape codes removed
+    line_widths: Vec<usize>,
+    // width of the widest line
+    width: usize,
+    // rendered label, empty if labels are hidden
+    label: String,
+    label_width: usize,
+    // image drawn over the lines, which are left blank for it
+    image: Option<String>,
+}
+
+impl Colorscript {
+    /// Width taken up by the colorscript or its label, whichever is wider
+    fn cell_width(&self) -> usize {
+        self.width.max(self.label_width)
+    }
 }
 
 // I HATE ANSI ESCAPE CHARACTERS
-fn print_colorscripts(
-    paths: &Vec<std::path::PathBuf>,
-    spacing: u8,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut max_widths = vec![];
-    let mut max_height = 0;
-    let mut file_contents: Vec<Vec<String>> = vec![];
+fn read_colorscripts(
+    sprites: &[crate::structs::Sprite],
+    options: &crate::structs::PrintOptions,
+) -> Result<Vec<Colorscript>, Box<dyn std::error::Error>> {
+    let mut colorscripts: Vec<Colorscript> = vec![];
+    let ansi_escape_regex = regex::Regex::new("\x1b\\[[^m]*m")?;
 
-    // first read all files and calculate maximum widths and heights by iterating through
+    // read all files and calculate the widths by iterating through
     // MUST IGNORE ANSI ESCAPE CODES❗❗❗
-    for path in paths {
-        let file = std::fs::File::open(path)?;
-        let reader = std::io::BufReader::new(file);
+    for sprite in sprites {
         // put all the lines in a vector
-        let mut lines: Vec<String> = vec![];
-        let mut max_width = 0;
+        let lines: Vec<String> = read_colorscript(&sprite.path, options)?
+            .lines()
+            .map(String::from)
+            .collect();
+        let mut line_widths: Vec<usize> = vec![];
 
-        // get the longest line length
-        for line in reader.lines() {
-            let line = line?;
+        let label = render_label(sprite, options);
+        let label_width = unicode_width::UnicodeWidthStr::width(label.as_str());
+
+        // color the label after measuring it
+        let label = match (
+            options.label_color && options.colors.enabled,
+            crate::label::dominant_color(&lines),
+        ) {
+            (true, Some(color)) => crate::label::colorize(&label, color, options.colors.depth),
+            _ => label,
+        };
+
+        // fit the colors to the terminal, or take them out
+        let lines: Vec<String> = lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| crate::color::render_line(line, row, options.size, &options.colors))
+            .collect();
+        for line in &lines {
             // remove ansi escape codes for width calculation
-            let plain_line = regex::Regex::new("\x1b\\[[^m]*m")?.replace_all(&line, "");
-            max_width = max_width.max(plain_line.chars().count());
-            lines.push(line);
+            let plain_line = ansi_escape_regex.replace_all(line, "");
+            line_widths.push(unicode_width::UnicodeWidthStr::width(plain_line.as_ref()));
         }
+        let width = line_widths.iter().copied().max().unwrap_or(0);
+
+        // images take up the same cells as the glyphs would
+        let (lines, line_widths, image) = match options.protocol {
+            crate::structs::Protocol::Blocks | crate::structs::Protocol::Auto => {
+                (lines, line_widths, None)
+            }
+            protocol => {
+                let image = crate::protocol::encode(
+                    &read_grid(&sprite.path, options)?,
+                    protocol,
+                    width,
+                    lines.len(),
+                )?;
+                (
+                    vec![" ".repeat(width); lines.len()],
+                    vec![width; lines.len()],
+                    Some(image),
+                )
+            }
+        };
+
+        colorscripts.push(Colorscript {
+            width,
+            lines,
+            line_widths,
+            label,
+            label_width,
+            image,
+        });
+    }
+
+    Ok(colorscripts)
+}
+
+/// Label of a colorscript from the template, empty if labels are hidden and it has no shiny indicator
+fn render_label(sprite: &crate::structs::Sprite, options: &crate::structs::PrintOptions) -> String {
+    let label = if options.hide_name {
+        String::new()
+    } else {
+        crate::label::render(&options.label, sprite)
+    };
+
+    if options.shiny_indicator {
+        crate::label::add_shiny_indicator(&label, sprite)
+    } else {
+        label
+    }
+}
+
+/// Read a colorscript file
+/// installs from before the packed sizes were added only have `small` and `big`,
+/// so the packed sizes are converted from `small` if their file is missing
+/// scaled colorscripts and ones with effects or color changes are converted from `small` as well, unless `fetch --heights` made them already
+/// the ascii charset is always rendered from the pixels of `small` or `big`
+fn read_colorscript(
+    path: &std::path::Path,
+    options: &crate::structs::PrintOptions,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let size = options.size;
 
-        // push the max width and lines to the respective vectors
-        max_widths.push(max_width);
-        max_height = max_height.max(lines.len());
-        file_contents.push(lines);
+    if let Some(file_path) = colorscript_file(path, options)? {
+        return read_file(&file_path);
     }
 
-    // construct spacing string
-    let separator = " ".repeat(spacing as usize);
+    let grid = read_grid(path, options)?;
+
+    match options.charset {
+        crate::structs::Charset::Unicode => Ok(crate::colorscript::write(&grid, size)),
+        crate::structs::Charset::Ascii => {
+            Ok(crate::colorscript::write_ascii(&grid, size, &options.ramp))
+        }
+    }
+}
+
+/// Path of the file that has the colorscript as the print options want it, if `fetch` wrote one
+/// `None` means it has to be converted from the pixels
+fn colorscript_file(
+    path: &std::path::Path,
+    options: &crate::structs::PrintOptions,
+) -> Result<Option<std::path::PathBuf>, Box<dyn std::error::Error>> {
+    let size = options.size;
+
+    if options.charset != crate::structs::Charset::Unicode
+        || options.scale != 1
+        || options.effects.any()
+        || options.palette.any()
+    {
+        return Ok(None);
+    }
+
+    match options.height {
+        // fitted with the default filter by `fetch`
+        Some(height) if options.filter == crate::structs::Filter::default() => {
+            let fitted_path = size_path(path, &size.directory(Some(height)))?;
+            Ok(fitted_path.exists().then_some(fitted_path))
+        }
+        Some(_) => Ok(None),
+        None => Ok((path.exists()
+            || size == crate::structs::Size::Small
+            || size == crate::structs::Size::Big)
+            .then(|| path.to_path_buf())),
+    }
+}
+
+/// Read the pixels of a colorscript, scaled and changed as the print options say
+fn read_grid(
+    path: &std::path::Path,
+    options: &crate::structs::PrintOptions,
+) -> Result<crate::structs::PixelGrid, Box<dyn std::error::Error>> {
+    // `small` has every pixel of the sprite
+    let mut grid = crate::colorscript::parse(
+        &read_file(&size_path(path, &crate::structs::Size::Small.to_string())?)?,
+        crate::structs::Size::Small,
+    );
+    if let Some(height) = options.height {
+        grid = crate::resize::fit_height(&grid, height, options.size, options.filter);
+    }
+    if options.palette.any() {
+        grid = crate::palette::apply(&grid, &options.palette);
+    }
+    if options.effects.any() {
+        grid = crate::effects::apply(&grid, &options.effects);
+    }
+    if options.scale > 1 {
+        grid = crate::resize::upscale(&grid, options.scale);
+    }
+
+    Ok(grid)
+}
+
+/// Read a colorscript file, naming it if that fails, e.g. when a size or form is missing
+fn read_file(path: &std::path::Path) -> Result<String, Box<dyn std::error::Error>> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+}
+
+/// Path of another directory's version of a colorscript
+/// e.g. `colorscripts/big/<regular or shiny>/<name>` to `colorscripts/small/<regular or shiny>/<name>`
+fn size_path(
+    path: &std::path::Path,
+    directory: &str,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let (Some(file_name), Some(shiny_directory)) = (
+        path.file_name(),
+        path.parent().and_then(|parent| parent.file_name()),
+    ) else {
+        return Err(format!("Invalid colorscript path {}", path.display()).into());
+    };
+
+    Ok(crate::constants::DATA_DIRECTORY
+        .join("colorscripts")
+        .join(directory)
+        .join(shiny_directory)
+        .join(file_name))
+}
+
+/// Width of the terminal, or `None` if the output is not a terminal
+fn terminal_width() -> Option<usize> {
+    match crossterm::terminal::size() {
+        Ok((width, _)) => Some(width as usize),
+        Err(_) => None,
+    }
+}
+
+/// Height of the terminal, or `None` if the output is not a terminal
+fn terminal_height() -> Option<usize> {
+    match crossterm::terminal::size() {
+        Ok((_, height)) => Some(height as usize),
+        Err(_) => None,
+    }
+}
+
+/// Pick the largest size that fits the terminal for `--size auto`
+/// sizes that fit every colorscript in as few rows as asked for are tried first, then ones that need more rows
+/// the smallest size is the last resort, and `small` is used if the output is not a terminal
+fn fit_size(
+    sprites: &[crate::structs::Sprite],
+    options: &crate::structs::PrintOptions,
+) -> Result<(Vec<crate::structs::Sprite>, crate::structs::PrintOptions), Box<dyn std::error::Error>>
+{
+    let sized = |size: crate::structs::Size| -> Result<_, Box<dyn std::error::Error>> {
+        let options = crate::structs::PrintOptions {
+            size,
+            ..options.clone()
+        };
+        let mut sprites = sprites.to_vec();
+        for sprite in &mut sprites {
+            sprite.path = size_path(&sprite.path, &size.to_string())?;
+        }
+        Ok((sprites, options))
+    };
+
+    let (Some(width), Some(height)) = (terminal_width(), terminal_height()) else {
+        return sized(crate::structs::Size::Small);
+    };
+    let available_width = width.saturating_sub(2 * options.margin as usize);
+    // leave a line for the prompt after the output
+    let available_height = height.saturating_sub(1);
+
+    // one row, or as many as `columns` makes
+    let fewest_rows = match options.columns {
+        Some(columns) => sprites.len().div_ceil((columns as usize).max(1)),
+        None => 1,
+    };
+
+    // the pixels of `small` are read at most once, every size is measured without drawing it
+    let mut grid_dimensions: Vec<Option<(usize, usize)>> = vec![None; sprites.len()];
+
+    let mut candidates: Vec<(crate::structs::Size, usize)> = vec![];
+    for size in crate::structs::Size::LARGEST_FIRST {
+        let (sized_sprites, sized_options) = sized(size)?;
+        let mut colorscripts: Vec<Colorscript> = vec![];
+        for (sprite, dimensions) in sized_sprites.iter().zip(&mut grid_dimensions) {
+            colorscripts.push(measure_colorscript(sprite, dimensions, &sized_options)?);
+        }
+        let rows = split_into_rows(&colorscripts, &sized_options);
+
+        let fits = rows
+            .iter()
+            .all(|row| row_width(&colorscripts[row.clone()], &sized_options) <= available_width)
+            && output_height(&colorscripts, &rows, &sized_options) <= available_height;
+        if fits {
+            candidates.push((size, rows.len()));
+        }
+    }
+
+    let size = candidates
+        .iter()
+        .find(|(_, rows)| *rows <= fewest_rows)
+        .or(candidates.first())
+        .map(|(size, _)| *size)
+        .unwrap_or(crate::structs::Size::Braille);
+
+    sized(size)
+}
+
+/// A blank colorscript as big as a sprite would be drawn, enough to measure rows with
+/// files written by `fetch` are only counted, otherwise the cells are worked out from the pixels
+/// the same way `read_grid` scales and changes them, and the pixels are kept for the next size
+fn measure_colorscript(
+    sprite: &crate::structs::Sprite,
+    grid_dimensions: &mut Option<(usize, usize)>,
+    options: &crate::structs::PrintOptions,
+) -> Result<Colorscript, Box<dyn std::error::Error>> {
+    let size = options.size;
+
+    let (glyphs, lines) = match colorscript_file(&sprite.path, options)? {
+        Some(file_path) => {
+            let colorscript = read_file(&file_path)?;
+            let glyphs = colorscript
+                .lines()
+                .map(|line| {
+                    unicode_width::UnicodeWidthStr::width(crate::color::strip(line).as_str())
+                })
+                .max()
+                .unwrap_or(0);
+            (glyphs, colorscript.lines().count())
+        }
+        None => {
+            let (width, height) = match grid_dimensions {
+                Some(dimensions) => *dimensions,
+                None => {
+                    let path = size_path(&sprite.path, &crate::structs::Size::Small.to_string())?;
+                    let grid =
+                        crate::colorscript::parse(&read_file(&path)?, crate::structs::Size::Small);
+                    *grid_dimensions.insert((grid.width, grid.height))
+                }
+            };
+            let (width, height) = match options.height {
+                Some(rows) => crate::resize::fitted_dimensions(width, height, rows, size),
+                None => (width, height),
+            };
+            let (width, height) = crate::effects::dimensions(width, height, &options.effects);
+            let scale = options.scale.max(1) as usize;
+
+            let (cell_width, cell_height) = size.cell_pixels();
+            (
+                (width * scale).div_ceil(cell_width) * size.cell_glyphs(),
+                (height * scale).div_ceil(cell_height),
+            )
+        }
+    };
+
+    let label = render_label(sprite, options);
+    let label_width = unicode_width::UnicodeWidthStr::width(label.as_str());
+
+    Ok(Colorscript {
+        lines: vec![String::new(); lines],
+        line_widths: vec![glyphs; lines],
+        width: glyphs,
+        label,
+        label_width,
+        image: None,
+    })
+}
+
+/// Number of lines taken up by rows of colorscripts, including labels and margins
+fn output_height(
+    colorscripts: &[Colorscript],
+    rows: &[std::ops::Range<usize>],
+    options: &crate::structs::PrintOptions,
+) -> usize {
+    let rows_height: usize = rows
+        .iter()
+        .map(|row| {
+            let label_height = if has_labels(&colorscripts[row.clone()], options) {
+                1
+            } else {
+                0
+            };
+            let artwork_height = colorscripts[row.clone()]
+                .iter()
+                .map(|colorscript| colorscript.lines.len())
+                .max()
+                .unwrap_or(0);
+            // every row ends with a line for the reset code
+            artwork_height + label_height + 1
+        })
+        .sum();
+
+    rows_height + 2 * options.margin as usize
+}
+
+/// Width of a row of colorscripts, including padding and spacing
+fn row_width(colorscripts: &[Colorscript], options: &crate::structs::PrintOptions) -> usize {
+    let cells_width: usize = colorscripts
+        .iter()
+        .map(|colorscript| colorscript.cell_width() + 2 * options.padding as usize)
+        .sum();
+    let separators_width = colorscripts.len().saturating_sub(1) * options.spacing as usize;
+
+    cells_width + separators_width
+}
+
+/// Number of spaces before a row, from the margin and the horizontal alignment
+fn row_offset(colorscripts: &[Colorscript], options: &crate::structs::PrintOptions) -> usize {
+    let margin = options.margin as usize;
+
+    // without a terminal there is nothing to align against
+    let available_width = match terminal_width() {
+        Some(width) => width.saturating_sub(2 * margin),
+        None => return margin,
+    };
+    let free_width = available_width.saturating_sub(row_width(colorscripts, options));
+
+    match options.align {
+        crate::structs::HorizontalAlignment::Left => margin,
+        crate::structs::HorizontalAlignment::Center => margin + free_width / 2,
+        crate::structs::HorizontalAlignment::Right => margin + free_width,
+    }
+}
+
+/// Split the colorscripts into rows, returned as ranges of indices
+/// with `columns` set, every row has that many colorscripts
+/// otherwise as many colorscripts as fit the terminal width go into a row
+fn split_into_rows(
+    colorscripts: &[Colorscript],
+    options: &crate::structs::PrintOptions,
+) -> Vec<std::ops::Range<usize>> {
+    let mut rows: Vec<std::ops::Range<usize>> = vec![];
+
+    if let Some(columns) = options.columns {
+        let columns = (columns as usize).max(1);
+        for start in (0..colorscripts.len()).step_by(columns) {
+            rows.push(start..(start + columns).min(colorscripts.len()));
+        }
+        return rows;
+    }
+
+    // if the output is not a terminal then keep everything in one row, markup is never fitted to one
+    let available_width =
+        match terminal_width().filter(|_| options.format == crate::structs::Format::Ansi) {
+            Some(width) => width.saturating_sub(2 * options.margin as usize),
+            None => usize::MAX,
+        };
+
+    let mut start = 0;
+    for i in 1..colorscripts.len() {
+        // always keep at least one colorscript in a row, even if it does not fit
+        if row_width(&colorscripts[start..=i], options) > available_width {
+            rows.push(start..i);
+            start = i;
+        }
+    }
+    if start < colorscripts.len() {
+        rows.push(start..colorscripts.len());
+    }
+
+    rows
+}
+
+/// Lines of a row of colorscripts, with their labels and the codes that draw their images
+fn row_lines(
+    colorscripts: &[Colorscript],
+    options: &crate::structs::PrintOptions,
+    offset: usize,
+) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    // every row gets its own height
+    let max_height = colorscripts
+        .iter()
+        .map(|colorscript| colorscript.lines.len())
+        .max()
+        .unwrap_or(0);
+
+    // reset code for the colors of the last artwork line, nothing to reset without colors
+    let reset = if options.colors.enabled {
+        "\x1b[0m"
+    } else {
+        ""
+    };
+
+    // construct spacing, padding and offset strings
+    let separator = " ".repeat(options.spacing as usize);
+    let padding = " ".repeat(options.padding as usize);
+    let indent = " ".repeat(offset);
+
+    // labels go on their own line, centered over or under every colorscript
+    let label_line = if !has_labels(colorscripts, options) {
+        None
+    } else {
+        let mut label_line = indent.clone();
+        for (file_index, colorscript) in colorscripts.iter().enumerate() {
+            let free_width = colorscript.cell_width() - colorscript.label_width;
+            push_cell(
+                &mut label_line,
+                &padding,
+                free_width / 2,
+                &colorscript.label,
+                free_width - free_width / 2,
+            );
+
+            if file_index < colorscripts.len() - 1 {
+                label_line.push_str(&separator);
+            }
+        }
+        Some(label_line.trim_end().to_string())
+    };
+
+    if options.label_position == crate::structs::LabelPosition::Above {
+        if let Some(label_line) = &label_line {
+            lines.push(label_line.clone());
+        }
+    }
 
     // print each combined line
     for line_index in 0..max_height {
-        let mut line_to_print = String::new();
+        let mut line_to_print = indent.clone();
 
         // construct the combined line
-        for (file_index, lines) in file_contents.iter().enumerate() {
-            if line_index < lines.len() {
-                line_to_print.push_str(&lines[line_index]);
-            }
+        for (file_index, colorscript) in colorscripts.iter().enumerate() {
+            // shift shorter artworks down depending on the vertical alignment
+            let top = options.valign.offset(colorscript.lines.len(), max_height);
+            let artwork_line_index = line_index.checked_sub(top);
 
-            // pad the rest of the line if this artwork is shorter
-            if line_index >= lines.len()
-                || line_index < lines.len()
-                    && lines[line_index].chars().count() < max_widths[file_index]
-            {
-                let current_length = lines.get(line_index).map_or(0, |l| l.chars().count());
-                line_to_print.push_str(&" ".repeat(max_widths[file_index] - current_length));
-            }
+            let line = artwork_line_index
+                .and_then(|i| colorscript.lines.get(i))
+                .map_or("", |line| line.as_str());
+
+            // pad the rest of the line if this artwork is shorter or narrower
+            // artworks narrower than their label are centered under it
+            let current_width = artwork_line_index
+                .and_then(|i| colorscript.line_widths.get(i))
+                .copied()
+                .unwrap_or(0);
+            let left_width = (colorscript.cell_width() - colorscript.width) / 2;
+            let right_width = colorscript.cell_width() - left_width - current_width;
+
+            push_cell(&mut line_to_print, &padding, left_width, line, right_width);
 
             // check if this is the last file to print
-            if file_index < file_contents.len() - 1 {
+            if file_index < colorscripts.len() - 1 {
                 // do not add separator to the last file
                 line_to_print.push_str(&separator);
             }
         }
 
-        // finally print the thing
-        println!("{}", line_to_print);
+        lines.push(line_to_print);
+    }
+
+    // the images are drawn over the blank artwork lines from the start of the next line
+    let mut next_line = image_codes(colorscripts, options, offset, max_height);
+
+    if options.label_position == crate::structs::LabelPosition::Below {
+        if let Some(label_line) = &label_line {
+            // reset the colors of the last artwork line first
+            lines.push(format!("{}{}{}", next_line, reset, label_line));
+            next_line = String::new();
+        }
     }
 
     // reset terminal color by printing a reset code
-    println!("\x1b[0m");
+    lines.push(format!("{}{}", next_line, reset));
 
-    Ok(())
+    lines
+}
+
+/// Codes that draw the images of a row of colorscripts, with the cursor on the line below the row
+/// the cursor is moved to the top left of every image and back, so the protocols can leave it anywhere
+fn image_codes(
+    colorscripts: &[Colorscript],
+    options: &crate::structs::PrintOptions,
+    offset: usize,
+    max_height: usize,
+) -> String {
+    let mut codes = String::new();
+    let mut column = offset;
+
+    for colorscript in colorscripts {
+        if let Some(image) = &colorscript.image {
+            // same positions as the lines of the colorscript
+            let top = options.valign.offset(colorscript.lines.len(), max_height);
+            let left = column
+                + options.padding as usize
+                + (colorscript.cell_width() - colorscript.width) / 2;
+
+            // save the cursor, go up to the image, draw it and restore the cursor
+            codes.push_str(&format!(
+                "\x1b7\x1b[{}A\x1b[{}G{}\x1b8",
+                max_height - top,
+                left + 1,
+                image
+            ));
+        }
+
+        column +=
+            colorscript.cell_width() + 2 * options.padding as usize + options.spacing as usize;
+    }
+
+    codes
+}
+
+/// Whether a row of colorscripts gets a label line
+/// hidden names still leave the shiny indicators, if there are any in the row
+fn has_labels(colorscripts: &[Colorscript], options: &crate::structs::PrintOptions) -> bool {
+    !options.hide_name
+        || colorscripts
+            .iter()
+            .any(|colorscript| !colorscript.label.is_empty())
+}
+
+/// Push a padded cell onto a line
+fn push_cell(
+    line: &mut String,
+    padding: &str,
+    left_width: usize,
+    content: &str,
+    right_width: usize,
+) {
+    line.push_str(padding);
+    line.push_str(&" ".repeat(left_width));
+    line.push_str(content);
+    line.push_str(&" ".repeat(right_width));
+    line.push_str(padding);
 }
@@ -0,0 +1,296 @@
+use std::io::IsTerminal;
+
+/// Guess the best graphics protocol of the terminal
+/// kitty and iTerm2 are recognized by their environment variables, sixel support is asked from the terminal
+/// anything else, or an output that is not a terminal, gets blocks
+pub fn detect() -> crate::structs::Protocol {
+    if !std::io::stdout().is_terminal() {
+        return crate::structs::Protocol::Blocks;
+    }
+
+    let term = std::env::var("TERM").unwrap_or_default();
+    let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term_program == "ghostty"
+    {
+        return crate::structs::Protocol::Kitty;
+    }
+    if term_program == "iTerm.app" || term_program == "WezTerm" {
+        return crate::structs::Protocol::Iterm;
+    }
+
+    // the primary device attributes list 4 for sixel graphics, e.g. `\x1b[?62;4;22c`
+    if let Some(response) =
+        crate::color::query_terminal(b"\x1b[c", |response| response.ends_with(b"c"))
+    {
+        let response = String::from_utf8_lossy(&response);
+        let sixel = response
+            .trim_start_matches("\x1b[?")
+            .trim_end_matches('c')
+            .split(';')
+            // the first one is the kind of terminal
+            .skip(1)
+            .any(|attribute| attribute == "4");
+        if sixel {
+            return crate::structs::Protocol::Sixel;
+        }
+    }
+
+    crate::structs::Protocol::Blocks
+}
+
+/// Size of a terminal cell in pixels, guessed if the terminal does not say
+pub fn cell_size() -> (usize, usize) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => (
+            (size.width / size.columns) as usize,
+            (size.height / size.rows) as usize,
+        ),
+        _ => crate::constants::DEFAULT_CELL_SIZE,
+    }
+}
+
+/// Encode a pixel grid as an image in a graphics protocol, to be drawn over a number of cells
+/// the pixels are scaled up by a whole number first, so that the terminal does not blur them
+pub fn encode(
+    grid: &crate::structs::PixelGrid,
+    protocol: crate::structs::Protocol,
+    columns: usize,
+    rows: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let (_, cell_height) = cell_size();
+    let factor = ((rows * cell_height) as f32 / grid.height.max(1) as f32)
+        .round()
+        .clamp(1.0, u8::MAX as f32) as u8;
+    let grid = crate::resize::upscale(grid, factor);
+
+    match protocol {
+        crate::structs::Protocol::Sixel => Ok(sixel(&grid)),
+        crate::structs::Protocol::Kitty => kitty(&grid, columns, rows),
+        crate::structs::Protocol::Iterm => iterm(&grid, columns, rows),
+        crate::structs::Protocol::Blocks | crate::structs::Protocol::Auto => Ok(String::new()),
+    }
+}
+
+/// Kitty graphics protocol, a PNG sent in chunks
+/// see https://sw.kovidgoyal.net/kitty/graphics-protocol
+fn kitty(
+    grid: &crate::structs::PixelGrid,
+    columns: usize,
+    rows: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let payload = base64(&crate::colorscript::to_png(grid)?);
+    // the payload is sent in chunks of at most 4096 bytes, every one but the last has `m=1`
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(4096).collect();
+
+    let mut image = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        let chunk = std::str::from_utf8(chunk)?;
+        if i == 0 {
+            // `C=1` keeps the cursor where it is, `q=2` keeps the terminal from answering
+            image.push_str(&format!(
+                "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                columns, rows, more, chunk
+            ));
+        } else {
+            image.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+
+    Ok(image)
+}
+
+/// iTerm2 inline image, a PNG sent as a file
+/// see https://iterm2.com/documentation-images.html
+fn iterm(
+    grid: &crate::structs::PixelGrid,
+    columns: usize,
+    rows: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let png = crate::colorscript::to_png(grid)?;
+
+    Ok(format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+        png.len(),
+        columns,
+        rows,
+        base64(&png)
+    ))
+}
+
+/// Sixel image, six rows of pixels at a time with one pass for every color in them
+/// see https://vt100.net/docs/vt3xx-gp/chapter14.html
+fn sixel(grid: &crate::structs::PixelGrid) -> String {
+    // most terminals only have 256 sixel colors, so the colors get coarser until they fit
+    let mut palette: Vec<(u8, u8, u8)> = vec![];
+    let mut indices: Vec<Option<usize>> = vec![];
+    for bits in 0..8 {
+        let mask = 0xff << bits;
+        palette.clear();
+        indices.clear();
+
+        for pixel in &grid.pixels {
+            if pixel[3] == 0 {
+                indices.push(None);
+                continue;
+            }
+            let color = (pixel[0] & mask, pixel[1] & mask, pixel[2] & mask);
+            let index = match palette.iter().position(|c| *c == color) {
+                Some(index) => index,
+                None => {
+                    palette.push(color);
+                    palette.len() - 1
+                }
+            };
+            indices.push(Some(index));
+        }
+
+        if palette.len() <= 256 {
+            break;
+        }
+    }
+
+    // the second parameter keeps the pixels that are not drawn transparent
+    let mut image = format!("\x1bP0;1;0q\"1;1;{};{}", grid.width, grid.height);
+    for (i, (r, g, b)) in palette.iter().enumerate() {
+        // sixel colors go from 0 to 100
+        let percent = |value: u8| value as u32 * 100 / 255;
+        image.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            percent(*r),
+            percent(*g),
+            percent(*b)
+        ));
+    }
+
+    for top in (0..grid.height).step_by(6) {
+        let band_height = (grid.height - top).min(6);
+        let band_index = |x: usize, dy: usize| indices[(top + dy) * grid.width + x];
+
+        let mut band_colors: Vec<usize> = (0..grid.width)
+            .flat_map(|x| (0..band_height).filter_map(move |dy| band_index(x, dy)))
+            .collect();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+
+        for color in band_colors {
+            image.push_str(&format!("#{}", color));
+
+            // runs of the same sixel are written once with a count
+            let sixels: Vec<char> = (0..grid.width)
+                .map(|x| {
+                    let bits = (0..band_height)
+                        .filter(|dy| band_index(x, *dy) == Some(color))
+                        .fold(0u8, |bits, dy| bits | (1 << dy));
+                    (63 + bits) as char
+                })
+                .collect();
+            for run in sixels.chunk_by(|a, b| a == b) {
+                if run.len() > 3 {
+                    image.push_str(&format!("!{}{}", run.len(), run[0]));
+                } else {
+                    image.extend(run.iter());
+                }
+            }
+
+            // back to the start of the band for the next color
+            image.push('$');
+        }
+
+        // down to the next band
+        image.push('-');
+    }
+
+    image.push_str("\x1b\\");
+    image
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::PixelGrid;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    #[test]
+    fn encodes_sixel() {
+        // a full band of red with a blue pixel in the top right, then a band of two rows with two red pixels
+        let mut grid = PixelGrid::new(5, 8);
+        for y in 0..6 {
+            for x in 0..5 {
+                grid.set(x, y, RED);
+            }
+        }
+        grid.set(4, 0, BLUE);
+        grid.set(0, 6, RED);
+        grid.set(1, 6, RED);
+
+        assert_eq!(
+            super::sixel(&grid),
+            concat!(
+                "\x1bP0;1;0q\"1;1;5;8",
+                // palette registers in the order the colors are first seen
+                "#0;2;100;0;0#1;2;0;0;100",
+                // runs longer than three are counted, every color ends at the start of the band again
+                "#0!4~}$#1!4?@$-",
+                // the last band has only two rows, and runs of three are written out
+                "#0@@???$-",
+                "\x1b\\",
+            )
+        );
+    }
+
+    #[test]
+    fn encodes_base64() {
+        // test vectors from RFC 4648
+        let cases = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(super::base64(input.as_bytes()), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn encodes_every_bit_of_base64() {
+        assert_eq!(super::base64(&[0, 0, 0]), "AAAA");
+        assert_eq!(super::base64(&[255, 255, 255]), "////");
+        assert_eq!(super::base64(&[0xfb, 0xef, 0xbe]), "++++");
+    }
+}
@@ -0,0 +1,91 @@
+/// Scale a pixel grid up by a whole number, every pixel becomes a square of pixels
+pub fn upscale(grid: &crate::structs::PixelGrid, factor: u8) -> crate::structs::PixelGrid {
+    let factor = factor.max(1) as usize;
+    let mut scaled_grid = crate::structs::PixelGrid::new(grid.width * factor, grid.height * factor);
+
+    for y in 0..scaled_grid.height {
+        for x in 0..scaled_grid.width {
+            scaled_grid.set(x, y, grid.get(x / factor, y / factor));
+        }
+    }
+
+    scaled_grid
+}
+
+/// Scale a pixel grid down to fit a number of rows at a size, keeping its aspect ratio
+/// grids that already fit are left as they are, use `upscale` to make them bigger
+pub fn fit_height(
+    grid: &crate::structs::PixelGrid,
+    rows: u16,
+    size: crate::structs::Size,
+    filter: crate::structs::Filter,
+) -> crate::structs::PixelGrid {
+    let (target_width, target_height) = fitted_dimensions(grid.width, grid.height, rows, size);
+    if (target_width, target_height) == (grid.width, grid.height) {
+        return grid.clone();
+    }
+
+    // filter premultiplied colors, so that the colorless transparent pixels do not darken the edges
+    let mut img: image::Rgba32FImage =
+        image::ImageBuffer::new(grid.width as u32, grid.height as u32);
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let [r, g, b, a] = grid
+            .get(x as usize, y as usize)
+            .map(|value| value as f32 / 255.0);
+        *pixel = image::Rgba([r * a, g * a, b * a, a]);
+    }
+
+    let resized_img = image::imageops::resize(
+        &img,
+        target_width as u32,
+        target_height as u32,
+        filter_type(filter),
+    );
+
+    let mut resized_grid = crate::structs::PixelGrid::new(target_width, target_height);
+    for (x, y, pixel) in resized_img.enumerate_pixels() {
+        let [r, g, b, a] = pixel.0;
+        // colorscripts have no partly transparent pixels, so the edges are cut at half
+        if a < 0.5 {
+            continue;
+        }
+        let channel = |value: f32| (value / a * 255.0).round().clamp(0.0, 255.0) as u8;
+        resized_grid.set(
+            x as usize,
+            y as usize,
+            [channel(r), channel(g), channel(b), 255],
+        );
+    }
+
+    resized_grid
+}
+
+/// Width and height of a pixel grid after `fit_height`, without scaling it
+pub fn fitted_dimensions(
+    width: usize,
+    height: usize,
+    rows: u16,
+    size: crate::structs::Size,
+) -> (usize, usize) {
+    let (_, cell_height) = size.cell_pixels();
+    let target_height = (rows as usize * cell_height).max(1);
+    if height <= target_height || width == 0 {
+        return (width, height);
+    }
+
+    let target_width = ((width * target_height) as f32 / height as f32)
+        .round()
+        .max(1.0) as usize;
+
+    (target_width, target_height)
+}
+
+fn filter_type(filter: crate::structs::Filter) -> image::imageops::FilterType {
+    match filter {
+        crate::structs::Filter::Nearest => image::imageops::FilterType::Nearest,
+        crate::structs::Filter::Triangle => image::imageops::FilterType::Triangle,
+        crate::structs::Filter::CatmullRom => image::imageops::FilterType::CatmullRom,
+        crate::structs::Filter::Gaussian => image::imageops::FilterType::Gaussian,
+        crate::structs::Filter::Lanczos3 => image::imageops::FilterType::Lanczos3,
+    }
+}
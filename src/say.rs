@@ -1,32 +1,61 @@
-pub fn say(text: &str) {
+pub fn say(
+    text: &str,
+    names: Vec<&String>,
+    forms: Vec<&String>,
+    colors: crate::structs::ColorOptions,
+    settings: crate::config::PrintSettings,
+    aliases: std::collections::BTreeMap<String, String>,
+) {
+    let options = crate::structs::PrintOptions {
+        aliases,
+        ..say_print_options(colors, settings)
+    };
+
     // first prioritise input text
     if !text.is_empty() {
         // if input text was provided
         let content = split_into_lines(text);
         print_speech_bubble(&content);
-        crate::print::print(
-            false,
-            vec![&"regular".to_string()],
-            true,
-            vec![&"random".to_string()],
-            vec![0],
-            0.0,
-            0,
-        )
+        crate::print::print(forms, names, vec![0], &options)
     } else {
         // if no input text was provided
         let buffer = read_from_stdin();
         let content = split_into_lines(buffer.as_str());
         print_speech_bubble(&content);
-        crate::print::print(
-            false,
-            vec![&"regular".to_string()],
-            true,
-            vec![&"random".to_string()],
-            vec![0],
-            0.0,
-            0,
-        )
+        crate::print::print(forms, names, vec![0], &options)
+    }
+}
+
+/// Print options for the speaker, following the print defaults of the config file
+/// the label, its position, the spacing and the protocol are fixed, as listed by `config show`
+fn say_print_options(
+    colors: crate::structs::ColorOptions,
+    settings: crate::config::PrintSettings,
+) -> crate::structs::PrintOptions {
+    crate::structs::PrintOptions {
+        size: settings.size.value,
+        charset: settings.charset.value,
+        ramp: settings.ramp.value,
+        scale: settings.scale.value,
+        height: settings.height.value,
+        filter: settings.filter.value,
+        palette: crate::structs::Palette {
+            daltonize: settings.daltonize.value,
+            ..Default::default()
+        },
+        colors,
+        hide_name: settings.hide_name.value,
+        label: crate::constants::SAY_LABEL.to_string(),
+        label_position: crate::structs::LabelPosition::Below,
+        label_color: settings.label_color.value,
+        shiny_indicator: settings.shiny_indicator.value || settings.daltonize.value.is_some(),
+        lang: settings.lang.value,
+        shiny_rate: settings.shiny.value,
+        spacing: 0,
+        align: settings.align.value,
+        margin: settings.margin.value,
+        padding: settings.padding.value,
+        ..Default::default()
     }
 }
 
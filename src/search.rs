@@ -0,0 +1,169 @@
+// maximum number of suggestions to show when nothing matches
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Lowercase the input and strip everything that is not a letter or a digit
+/// e.g. `Mr. Mime`, `mr mime` and `mr-mime` all become `mrmime`
+/// accents and gender symbols are folded too, so `Flabébé` and `Nidoran♀` match their slugs
+pub fn normalize(input: &str) -> String {
+    input
+        .chars()
+        .map(fold_char)
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn fold_char(c: char) -> char {
+    match c {
+        'é' | 'è' | 'ê' | 'ë' | 'É' => 'e',
+        'á' | 'à' | 'â' | 'ä' => 'a',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        '♀' => 'f',
+        '♂' => 'm',
+        _ => c,
+    }
+}
+
+/// Find the candidate that matches the query, ignoring case and punctuation
+pub fn find_match<'a, I>(query: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let query = normalize(query);
+
+    candidates
+        .into_iter()
+        .find(|candidate| normalize(candidate) == query)
+}
+
+/// Get the closest candidates to the query, sorted by edit distance
+/// candidates that are too far away from the query are left out
+pub fn suggest<'a, I>(query: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let query = normalize(query);
+
+    // allow roughly one typo for every three characters
+    let threshold = (query.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let normalized = normalize(candidate);
+            let mut distance = levenshtein(&query, &normalized);
+
+            // treat prefixes as close matches, e.g. `charm` for `charmander`
+            if !query.is_empty() && normalized.starts_with(&query) {
+                distance = distance.min(1);
+            }
+
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+
+    // stable sort keeps the original order (pokedex order) for ties
+    scored.sort_by_key(|(distance, _)| *distance);
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Format suggestions into a hint line, or an empty string if there are none
+pub fn format_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!("Did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+/// Levenshtein edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+
+    // only keep the previous row of the distance matrix
+    let mut previous_row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row: Vec<usize> = vec![i + 1];
+
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+
+            let value = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+
+            current_row.push(value);
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    const CANDIDATES: [&str; 7] = [
+        "bulbasaur",
+        "charmander",
+        "charmeleon",
+        "charizard",
+        "pikachu",
+        "mr-mime",
+        "nidoran-f",
+    ];
+
+    #[test]
+    fn normalizes_case_punctuation_and_accents() {
+        let cases = [
+            ("Mr. Mime", "mrmime"),
+            ("mr-mime", "mrmime"),
+            ("Flabébé", "flabebe"),
+            ("Nidoran♀", "nidoranf"),
+            ("Type: Null", "typenull"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(super::normalize(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn suggests_the_closest_candidates() {
+        let cases: [(&str, &[&str]); 7] = [
+            // one typo
+            ("pikachuu", &["pikachu"]),
+            ("bulbsaur", &["bulbasaur"]),
+            // prefixes count as close, ties keep the pokedex order
+            ("charm", &["charmander", "charmeleon"]),
+            ("char", &["charmander", "charmeleon", "charizard"]),
+            // punctuation and case do not count as typos
+            ("Mr Mime", &["mr-mime"]),
+            ("nidoran♀", &["nidoran-f"]),
+            // too far from everything
+            ("xyz", &[]),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(super::suggest(query, CANDIDATES), expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn formats_suggestions_as_a_hint() {
+        assert_eq!(super::format_suggestions(&[]), "");
+        assert_eq!(
+            super::format_suggestions(&["charmander".to_string(), "charmeleon".to_string()]),
+            "Did you mean: charmander, charmeleon?"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+/// Tile pixel grids and their labels into one grid, row by row
+/// with `columns` set, every row has that many cells, otherwise the sheet is about as wide as it is high
+/// spacing, padding and margins follow the print options, counted in pixels
+pub fn tile(
+    cells: &[(crate::structs::PixelGrid, String)],
+    options: &crate::structs::PrintOptions,
+) -> crate::structs::PixelGrid {
+    let columns = match options.columns {
+        Some(columns) => (columns as usize).max(1),
+        None => (cells.len() as f32).sqrt().ceil().max(1.0) as usize,
+    };
+    let rows = cells.len().div_ceil(columns);
+
+    let padding = options.padding as usize;
+    let spacing = options.spacing as usize;
+    let margin = options.margin as usize;
+
+    // every cell is as big as the biggest colorscript and label, so the sheet lines up
+    let artwork_width = cells
+        .iter()
+        .map(|(grid, label)| grid.width.max(text_width(label)))
+        .max()
+        .unwrap_or(0);
+    let artwork_height = cells.iter().map(|(grid, _)| grid.height).max().unwrap_or(0);
+    // a blank pixel above and below the text
+    let label_height = if cells.iter().any(|(_, label)| text_width(label) > 0) {
+        GLYPH_HEIGHT + 2
+    } else {
+        0
+    };
+    let cell_width = artwork_width + 2 * padding;
+    let cell_height = artwork_height + label_height;
+
+    let mut sheet = crate::structs::PixelGrid::new(
+        2 * margin + columns * cell_width + (columns - 1) * spacing,
+        2 * margin + rows * cell_height + rows.saturating_sub(1) * spacing,
+    );
+
+    for (i, (grid, label)) in cells.iter().enumerate() {
+        let left = margin + (i % columns) * (cell_width + spacing) + padding;
+        let top = margin + (i / columns) * (cell_height + spacing);
+        let (label_top, artwork_top) = match options.label_position {
+            crate::structs::LabelPosition::Above => (top + 1, top + label_height),
+            crate::structs::LabelPosition::Below => (top + artwork_height + 1, top),
+        };
+
+        // shift shorter artworks down depending on the vertical alignment
+        let artwork_top = artwork_top + options.valign.offset(grid.height, artwork_height);
+        let artwork_left = left + (artwork_width - grid.width) / 2;
+
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let pixel = grid.get(x, y);
+                if pixel[3] != 0 {
+                    sheet.set(artwork_left + x, artwork_top + y, pixel);
+                }
+            }
+        }
+
+        draw_text(
+            &mut sheet,
+            label,
+            left + (artwork_width - text_width(label)) / 2,
+            label_top,
+            crate::constants::SHEET_LABEL_COLOR.to_pixel(),
+        );
+    }
+
+    sheet
+}
+
+/// Draw text in the built-in font with its top left corner at a position
+fn draw_text(
+    grid: &mut crate::structs::PixelGrid,
+    text: &str,
+    left: usize,
+    top: usize,
+    pixel: [u8; 4],
+) {
+    for (i, glyph) in glyphs(text).iter().enumerate() {
+        for (y, row) in glyph.iter().enumerate() {
+            for x in 0..GLYPH_WIDTH {
+                if row >> (GLYPH_WIDTH - 1 - x) & 1 == 1 {
+                    grid.set(left + i * (GLYPH_WIDTH + 1) + x, top + y, pixel);
+                }
+            }
+        }
+    }
+}
+
+/// Width of text in the built-in font, with a blank pixel between glyphs
+fn text_width(text: &str) -> usize {
+    (glyphs(text).len() * (GLYPH_WIDTH + 1)).saturating_sub(1)
+}
+
+/// Glyphs of the characters of text that the built-in font has, the rest are left out
+fn glyphs(text: &str) -> Vec<[u8; GLYPH_HEIGHT]> {
+    let text: String = text.chars().filter(|c| glyph(*c).is_some()).collect();
+    text.trim().chars().filter_map(glyph).collect()
+}
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+/// Rows of a character in the built-in font, the lowest three bits from left to right
+/// the font only has capitals, so labels such as `{name} #{dex}` are written in them
+fn glyph(c: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    let glyph = match c.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        // for names such as `Flabébé`
+        'E' | 'é' | 'É' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '[' => [0b011, 0b010, 0b010, 0b010, 0b011],
+        ']' => [0b110, 0b010, 0b010, 0b010, 0b110],
+        _ => return None,
+    };
+
+    Some(glyph)
+}
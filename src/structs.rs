@@ -1,6 +1,17 @@
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct Name {
+    pub eng: String,
+    // every other language, e.g. `jpn`, `jpn_ro` and `chs`
+    #[serde(flatten)]
+    pub others: std::collections::BTreeMap<String, String>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct Slug {
     pub eng: String,
+    // every other language, e.g. `jpn` and `jpn_ro`
+    #[serde(flatten)]
+    pub others: std::collections::BTreeMap<String, String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -16,6 +27,7 @@ pub struct Generation {
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct PokemonRaw {
     pub idx: String,
+    pub name: Name,
     pub slug: Slug,
     #[serde(rename = "gen-8")]
     pub gen_8: Generation,
@@ -30,11 +42,882 @@ pub struct PokemonRawCollection {
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Pokemon {
     pub pokedex: String,
+    // slug, used for file names
     pub name: String,
+    // proper name, e.g. `Mr. Mime` for `mr-mime`
+    // missing in `pokemon.json` generated by older versions
+    #[serde(default)]
+    pub display_name: String,
+    // names and slugs in every other language, keyed by language
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub names: std::collections::BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub slugs: std::collections::BTreeMap<String, String>,
     pub forms: Vec<String>,
 }
 
+impl Pokemon {
+    /// Proper name of the Pokemon, falling back to the slug
+    pub fn get_display_name(&self) -> &str {
+        if self.display_name.is_empty() {
+            &self.name
+        } else {
+            &self.display_name
+        }
+    }
+
+    /// Name of the Pokemon in a language, falling back to the proper English name
+    pub fn get_name(&self, language: &str) -> &str {
+        match language_key(language) {
+            crate::constants::DEFAULT_LANGUAGE => self.get_display_name(),
+            key => self
+                .names
+                .get(key)
+                .map(|name| name.as_str())
+                .unwrap_or(self.get_display_name()),
+        }
+    }
+
+    /// Every name the Pokemon can be looked up by, in every language
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = vec![self.name.as_str(), self.get_display_name()];
+        names.extend(self.names.values().map(|name| name.as_str()));
+        names.extend(self.slugs.values().map(|slug| slug.as_str()));
+        names
+    }
+}
+
+/// Map common language codes to the ones used by pokesprite
+/// e.g. `ja` to `jpn`, `zh` to `chs`
+pub fn language_key(language: &str) -> &str {
+    match language {
+        "en" | "eng" | "english" => "eng",
+        "ja" | "jp" | "jpn" | "japanese" => "jpn",
+        "ja_ro" | "jp_ro" | "jpn_ro" | "romaji" => "jpn_ro",
+        "zh" | "chs" | "chinese" => "chs",
+        _ => language,
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct PokemonCollection {
     pub pokemons: Vec<Pokemon>,
 }
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+// misspelled keys are errors instead of being ignored, e.g. `hide-name` for `hide_name`
+#[serde(deny_unknown_fields)]
+pub struct PrintDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<HorizontalAlignment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<Rgb>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub big: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<Charset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daltonize: Option<ColorDeficiency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_name: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_color: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_position: Option<LabelPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<Protocol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ramp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shiny: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shiny_indicator: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<Size>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valign: Option<VerticalAlignment>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FetchDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpha_threshold: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend: Option<Rgb>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extract_destination: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heights: Option<Vec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default)]
+    pub print: PrintDefaults,
+    #[serde(default)]
+    pub fetch: FetchDefaults,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // defaults for the subcommands, below the command line flags
+    #[serde(default)]
+    pub print: PrintDefaults,
+    #[serde(default)]
+    pub fetch: FetchDefaults,
+    // named sets of defaults chosen with `--profile`, above the defaults
+    #[serde(default)]
+    pub profiles: std::collections::BTreeMap<String, Profile>,
+    // nickname to selector, e.g. `sparky = "pikachu:shiny"`
+    #[serde(default)]
+    pub aliases: std::collections::BTreeMap<String, String>,
+    // team name to list of selectors
+    #[serde(default)]
+    pub teams: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+/// A colorscript picked for printing, and what it shows
+#[derive(Debug, Clone)]
+pub struct Sprite {
+    pub path: std::path::PathBuf,
+    pub name: String,
+    pub pokedex: u16,
+    pub form: String,
+    pub shiny: bool,
+}
+
+/// A colorscript as a grid of RGBA pixels, row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelGrid {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl PixelGrid {
+    /// A fully transparent grid
+    pub fn new(width: usize, height: usize) -> Self {
+        PixelGrid {
+            width,
+            height,
+            pixels: vec![[0, 0, 0, 0]; width * height],
+        }
+    }
+
+    /// Pixel at a position, transparent if out of bounds
+    pub fn get(&self, x: usize, y: usize) -> [u8; 4] {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x]
+        } else {
+            [0, 0, 0, 0]
+        }
+    }
+
+    /// Set the pixel at a position, ignored if out of bounds
+    pub fn set(&mut self, x: usize, y: usize, pixel: [u8; 4]) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = pixel;
+        }
+    }
+}
+
+/// A color written as hex, e.g. `#1e1e2e`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl std::str::FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Rgb(r, g, b)),
+            _ => Err(format!("Invalid color: {}, expected hex like `#1e1e2e`", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl Rgb {
+    /// The color as an opaque pixel
+    pub fn to_pixel(&self) -> [u8; 4] {
+        [self.0, self.1, self.2, 255]
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(color: Rgb) -> Self {
+        color.to_string()
+    }
+}
+
+/// Where labels go relative to their colorscript
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelPosition {
+    #[default]
+    Above,
+    Below,
+}
+
+impl std::str::FromStr for LabelPosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "above" => Ok(LabelPosition::Above),
+            "below" => Ok(LabelPosition::Below),
+            _ => Err(format!("Invalid label position: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for LabelPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LabelPosition::Above => write!(f, "above"),
+            LabelPosition::Below => write!(f, "below"),
+        }
+    }
+}
+
+/// Vertical position of shorter colorscripts within a row
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Center,
+    // shared ground line
+    Bottom,
+}
+
+impl VerticalAlignment {
+    /// How far down to shift something of a height within a taller row
+    pub fn offset(&self, height: usize, max_height: usize) -> usize {
+        let free_height = max_height - height;
+        match self {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Center => free_height / 2,
+            VerticalAlignment::Bottom => free_height,
+        }
+    }
+}
+
+/// Horizontal position of every row within the terminal width
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HorizontalAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Whether to write color escape codes
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    // decided by the terminal and environment variables
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Resolution of a colorscript, every size has its own directory
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Size {
+    // two glyphs for every pixel, `██`
+    Big,
+    // two pixels stacked in a cell, `▀` and `▄`
+    #[default]
+    Small,
+    // 2x2 pixels in a cell, quadrant blocks such as `▙`
+    #[serde(alias = "tiny")]
+    Quadrant,
+    // 2x3 pixels in a cell, sextant blocks such as `🬗`
+    Sextant,
+    // 2x4 pixels in a cell, braille dots such as `⣿`
+    #[serde(alias = "micro")]
+    Braille,
+    // the largest of the others that fits the terminal, picked when printing
+    Auto,
+}
+
+impl Size {
+    /// Every size, in the order they are generated
+    pub const ALL: [Size; 5] = [
+        Size::Small,
+        Size::Big,
+        Size::Quadrant,
+        Size::Sextant,
+        Size::Braille,
+    ];
+
+    /// Sizes `fetch --heights` fits to every height, the packed ones are quick to convert when printing
+    pub const FITTED: [Size; 2] = [Size::Small, Size::Big];
+
+    /// Sizes `auto` picks from, largest first
+    pub const LARGEST_FIRST: [Size; 5] = [
+        Size::Big,
+        Size::Small,
+        Size::Quadrant,
+        Size::Sextant,
+        Size::Braille,
+    ];
+
+    /// Number of pixels across and down in a single cell of the colorscript
+    /// big is the odd one out, with a pixel taking up two cells
+    pub fn cell_pixels(&self) -> (usize, usize) {
+        match self {
+            Size::Big => (1, 1),
+            Size::Small | Size::Auto => (1, 2),
+            Size::Quadrant => (2, 2),
+            Size::Sextant => (2, 3),
+            Size::Braille => (2, 4),
+        }
+    }
+
+    /// Number of glyphs across a cell of pixels, two for the `██` of big
+    pub fn cell_glyphs(&self) -> usize {
+        match self {
+            Size::Big => 2,
+            _ => 1,
+        }
+    }
+
+    /// Directory of the colorscripts, e.g. `small`, or `small-12` for ones fitted to 12 rows by `fetch --heights`
+    pub fn directory(&self, height: Option<u16>) -> String {
+        match height {
+            Some(height) => format!("{}-{}", self, height),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "big" => Ok(Size::Big),
+            "small" => Ok(Size::Small),
+            "tiny" | "quadrant" => Ok(Size::Quadrant),
+            "sextant" => Ok(Size::Sextant),
+            "micro" | "braille" => Ok(Size::Braille),
+            "auto" => Ok(Size::Auto),
+            _ => Err(format!("Invalid size: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Size::Big => write!(f, "big"),
+            Size::Small => write!(f, "small"),
+            Size::Quadrant => write!(f, "quadrant"),
+            Size::Sextant => write!(f, "sextant"),
+            Size::Braille => write!(f, "braille"),
+            Size::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Characters colorscripts are drawn with
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Charset {
+    // block and braille glyphs
+    #[default]
+    Unicode,
+    // a ramp of ascii characters from dark to bright, for terminals without the glyphs
+    Ascii,
+}
+
+impl std::str::FromStr for Charset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unicode" => Ok(Charset::Unicode),
+            "ascii" => Ok(Charset::Ascii),
+            _ => Err(format!("Invalid charset: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Charset::Unicode => write!(f, "unicode"),
+            Charset::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+/// How colorscripts are drawn in the terminal
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    // glyphs with colors, works everywhere
+    #[default]
+    Blocks,
+    Sixel,
+    Kitty,
+    Iterm,
+    // the best one the terminal supports, picked when printing
+    Auto,
+}
+
+impl std::str::FromStr for Protocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(Protocol::Blocks),
+            "sixel" => Ok(Protocol::Sixel),
+            "kitty" => Ok(Protocol::Kitty),
+            "iterm" => Ok(Protocol::Iterm),
+            "auto" => Ok(Protocol::Auto),
+            _ => Err(format!("Invalid protocol: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Protocol::Blocks => write!(f, "blocks"),
+            Protocol::Sixel => write!(f, "sixel"),
+            Protocol::Kitty => write!(f, "kitty"),
+            Protocol::Iterm => write!(f, "iterm"),
+            Protocol::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// What `print` writes, escape codes for the terminal or a document for other places
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Ansi,
+    // the glyphs in a `<pre>`, keeping the half-block look
+    Html,
+    // every pixel as a square
+    Svg,
+    // the pixels as an image, only for `export`
+    Png,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "html" => Ok(Format::Html),
+            "svg" => Ok(Format::Svg),
+            "png" => Ok(Format::Png),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Format::Ansi => write!(f, "ansi"),
+            Format::Html => write!(f, "html"),
+            Format::Svg => write!(f, "svg"),
+            Format::Png => write!(f, "png"),
+        }
+    }
+}
+
+/// What goes on a sheet of every Pokemon
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sheet {
+    // every form instead of only the regular ones
+    pub forms: bool,
+    // the shiny version next to every regular one
+    pub shiny: bool,
+}
+
+/// Kind of color blindness to correct colors for
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorDeficiency {
+    // no red cones
+    Protanopia,
+    // no green cones
+    Deuteranopia,
+    // no blue cones
+    Tritanopia,
+}
+
+impl std::str::FromStr for ColorDeficiency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "protanopia" => Ok(ColorDeficiency::Protanopia),
+            "deuteranopia" => Ok(ColorDeficiency::Deuteranopia),
+            "tritanopia" => Ok(ColorDeficiency::Tritanopia),
+            _ => Err(format!("Invalid color deficiency: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorDeficiency {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ColorDeficiency::Protanopia => write!(f, "protanopia"),
+            ColorDeficiency::Deuteranopia => write!(f, "deuteranopia"),
+            ColorDeficiency::Tritanopia => write!(f, "tritanopia"),
+        }
+    }
+}
+
+/// Filter used when scaling colorscripts down, see `image::imageops::FilterType`
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Filter {
+    Nearest,
+    // averages the pixels, keeps pixel art the least blurry
+    #[default]
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+impl std::str::FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nearest" => Ok(Filter::Nearest),
+            "triangle" => Ok(Filter::Triangle),
+            "catmull-rom" => Ok(Filter::CatmullRom),
+            "gaussian" => Ok(Filter::Gaussian),
+            "lanczos3" => Ok(Filter::Lanczos3),
+            _ => Err(format!("Invalid filter: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Filter::Nearest => write!(f, "nearest"),
+            Filter::Triangle => write!(f, "triangle"),
+            Filter::CatmullRom => write!(f, "catmull-rom"),
+            Filter::Gaussian => write!(f, "gaussian"),
+            Filter::Lanczos3 => write!(f, "lanczos3"),
+        }
+    }
+}
+
+/// Number of colors the terminal can show
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorDepth {
+    // decided by `COLORTERM` and `TERM`
+    #[default]
+    Auto,
+    TrueColor,
+    // xterm 256 color palette
+    Ansi256,
+    // the basic 16 colors, e.g. the linux console
+    Ansi16,
+}
+
+/// Effects applied to the pixels of colorscripts when printing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Effects {
+    // mirror horizontally
+    pub flip: bool,
+    pub outline: Option<Rgb>,
+    pub shadow: bool,
+    // color to paint every opaque pixel in
+    pub silhouette: Option<Rgb>,
+}
+
+impl Effects {
+    /// Whether any effect is picked
+    pub fn any(&self) -> bool {
+        self.flip || self.outline.is_some() || self.shadow || self.silhouette.is_some()
+    }
+}
+
+/// Color changes applied to colorscripts when printing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Palette {
+    // degrees to rotate the hues by
+    pub hue_shift: f32,
+    pub grayscale: bool,
+    pub invert: bool,
+    // color blindness to shift the colors for
+    pub daltonize: Option<ColorDeficiency>,
+    // background to keep the colors apart from, set by `--light-bg`
+    pub background: Option<Rgb>,
+}
+
+impl Palette {
+    /// Whether any color change is picked
+    pub fn any(&self) -> bool {
+        self.hue_shift != 0.0
+            || self.grayscale
+            || self.invert
+            || self.daltonize.is_some()
+            || self.background.is_some()
+    }
+}
+
+/// How partly transparent pixels of the sprites are converted
+#[derive(Debug, Clone, Copy)]
+pub struct AlphaOptions {
+    // pixels less opaque than this are left out
+    pub threshold: u8,
+    // background to blend the rest against, drawn at full color otherwise
+    pub blend: Option<Rgb>,
+}
+
+/// How colors are written, resolved from the global flags
+#[derive(Debug, Clone, Copy)]
+pub struct ColorOptions {
+    // write color escape codes, plain glyphs otherwise
+    pub enabled: bool,
+    pub depth: ColorDepth,
+    // ordered dithering when reducing the colors
+    pub dither: bool,
+}
+
+impl Default for ColorOptions {
+    fn default() -> Self {
+        ColorOptions {
+            enabled: true,
+            depth: ColorDepth::TrueColor,
+            dither: false,
+        }
+    }
+}
+
+impl std::str::FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            _ => Err(format!("Invalid color mode: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ColorMode::Auto => write!(f, "auto"),
+            ColorMode::Always => write!(f, "always"),
+            ColorMode::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl std::str::FromStr for ColorDepth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorDepth::Auto),
+            "truecolor" | "24bit" => Ok(ColorDepth::TrueColor),
+            "256" => Ok(ColorDepth::Ansi256),
+            "16" => Ok(ColorDepth::Ansi16),
+            _ => Err(format!("Invalid color depth: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorDepth {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ColorDepth::Auto => write!(f, "auto"),
+            ColorDepth::TrueColor => write!(f, "truecolor"),
+            ColorDepth::Ansi256 => write!(f, "256"),
+            ColorDepth::Ansi16 => write!(f, "16"),
+        }
+    }
+}
+
+impl std::str::FromStr for VerticalAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(VerticalAlignment::Top),
+            "center" => Ok(VerticalAlignment::Center),
+            "bottom" => Ok(VerticalAlignment::Bottom),
+            _ => Err(format!("Invalid vertical alignment: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for VerticalAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VerticalAlignment::Top => write!(f, "top"),
+            VerticalAlignment::Center => write!(f, "center"),
+            VerticalAlignment::Bottom => write!(f, "bottom"),
+        }
+    }
+}
+
+impl std::str::FromStr for HorizontalAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(HorizontalAlignment::Left),
+            "center" => Ok(HorizontalAlignment::Center),
+            "right" => Ok(HorizontalAlignment::Right),
+            _ => Err(format!("Invalid alignment: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for HorizontalAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HorizontalAlignment::Left => write!(f, "left"),
+            HorizontalAlignment::Center => write!(f, "center"),
+            HorizontalAlignment::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// Options for how colorscripts are printed
+#[derive(Debug, Clone)]
+pub struct PrintOptions {
+    pub size: Size,
+    // graphics protocol for the images, or blocks for glyphs
+    pub protocol: Protocol,
+    pub charset: Charset,
+    // characters from dark to bright for the ascii charset
+    pub ramp: String,
+    // whole number the pixels are scaled up by, 1 leaves them as they are
+    pub scale: u8,
+    // number of rows to scale the colorscripts down to, if they are taller
+    pub height: Option<u16>,
+    pub filter: Filter,
+    pub effects: Effects,
+    pub palette: Palette,
+    pub colors: ColorOptions,
+    pub hide_name: bool,
+    // template for the label of every colorscript, e.g. `{name} #{dex}`
+    pub label: String,
+    pub label_position: LabelPosition,
+    // color labels with the dominant color of their colorscript
+    pub label_color: bool,
+    // mark shiny colorscripts in their label, so shininess does not depend on colors alone
+    pub shiny_indicator: bool,
+    // language of the names in labels, e.g. `jpn`
+    pub lang: String,
+    pub shiny_rate: f32,
+    pub spacing: u8,
+    // maximum number of colorscripts per row, fits the terminal width if not set
+    pub columns: Option<u16>,
+    pub valign: VerticalAlignment,
+    pub align: HorizontalAlignment,
+    // blank lines above and below the output, and columns to the sides of it
+    pub margin: u8,
+    // columns on both sides of every colorscript
+    pub padding: u8,
+    pub format: Format,
+    // file the markup formats and png are written to, stdout if not set
+    pub output: Option<std::path::PathBuf>,
+    // every Pokemon instead of the ones asked for
+    pub sheet: Option<Sheet>,
+    // nicknames from the config file, expanded before names are looked up
+    pub aliases: std::collections::BTreeMap<String, String>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            size: Size::Small,
+            protocol: Protocol::Blocks,
+            charset: Charset::Unicode,
+            ramp: crate::constants::DEFAULT_RAMP.to_string(),
+            scale: 1,
+            height: None,
+            filter: Filter::Triangle,
+            effects: Effects::default(),
+            palette: Palette::default(),
+            colors: ColorOptions::default(),
+            hide_name: false,
+            label: crate::constants::DEFAULT_LABEL.to_string(),
+            label_position: LabelPosition::Above,
+            label_color: false,
+            shiny_indicator: false,
+            lang: crate::constants::DEFAULT_LANGUAGE.to_string(),
+            shiny_rate: crate::constants::DEFAULT_SHINY_RATE,
+            spacing: crate::constants::DEFAULT_SPACING,
+            columns: None,
+            valign: VerticalAlignment::Top,
+            align: HorizontalAlignment::Left,
+            margin: 0,
+            padding: 0,
+            format: Format::Ansi,
+            output: None,
+            sheet: None,
+            aliases: std::collections::BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+pub fn add(team: &str, selectors: Vec<&String>) -> Result<(), Box<dyn std::error::Error>> {
+    // only the `[teams]` table is changed, the rest of the file stays as the user wrote it
+    let mut document = crate::config::read_config_document()?;
+
+    // a typo would break every later `print --team`, so it is rejected before saving
+    let aliases = document_aliases(&document);
+    for selector in &selectors {
+        check_selector(selector, &aliases)?;
+    }
+    let teams = document
+        .entry("teams")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or("`teams` in the config file is not a table")?;
+
+    // create the team if it does not exist yet
+    let team_selectors = teams
+        .entry(team)
+        .or_insert(toml_edit::value(toml_edit::Array::new()))
+        .as_array_mut()
+        .ok_or(format!("Team {} in the config file is not a list", team))?;
+    for selector in selectors {
+        team_selectors.push(selector.as_str());
+    }
+    team_selectors.fmt();
+    let team_size = team_selectors.len();
+
+    crate::config::write_config_document(&document)?;
+
+    println!("Team {} now has {} Pokemon", team, team_size);
+
+    Ok(())
+}
+
+pub fn remove(team: &str, selectors: Vec<&String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut document = crate::config::read_config_document()?;
+
+    let Some(teams) = document
+        .get_mut("teams")
+        .and_then(|teams| teams.as_table_like_mut())
+        .filter(|teams| teams.contains_key(team))
+    else {
+        return Err(format!(
+            "Team {} not found\nHint: Do `rustmon team list` to see all saved teams.",
+            team
+        )
+        .into());
+    };
+
+    if selectors.is_empty() {
+        // no pokemon specified, remove the whole team
+        teams.remove(team);
+        println!("Removed team {}", team);
+    } else {
+        let team_selectors = teams
+            .get_mut(team)
+            .and_then(|team_selectors| team_selectors.as_array_mut())
+            .ok_or(format!("Team {} in the config file is not a list", team))?;
+        for selector in selectors {
+            // case and punctuation insensitive comparison
+            let query = crate::search::normalize(selector);
+            let position = team_selectors.iter().position(|s| {
+                s.as_str()
+                    .is_some_and(|s| crate::search::normalize(s) == query)
+            });
+            match position {
+                Some(pos) => {
+                    team_selectors.remove(pos);
+                }
+                None => println!("{} is not in team {}, skipping", selector, team),
+            }
+        }
+        team_selectors.fmt();
+        println!("Team {} now has {} Pokemon", team, team_selectors.len());
+    }
+
+    crate::config::write_config_document(&document)?;
+
+    Ok(())
+}
+
+/// Aliases saved in a config document, without reading the rest of it
+fn document_aliases(
+    document: &toml_edit::DocumentMut,
+) -> std::collections::BTreeMap<String, String> {
+    document
+        .get("aliases")
+        .and_then(|aliases| aliases.as_table_like())
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter_map(|(alias, selector)| {
+                    Some((alias.to_string(), selector.as_str()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Check that every Pokemon in a selector exists, and has the form asked for
+fn check_selector(
+    selector: &str,
+    aliases: &std::collections::BTreeMap<String, String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tokens: Vec<String> = selector.split_whitespace().map(String::from).collect();
+    let selections = crate::forms::parse_names(&tokens.iter().collect::<Vec<&String>>(), aliases)?;
+
+    let contents = std::fs::read_to_string(crate::constants::DATA_DIRECTORY.join("pokemon.json"))?;
+    let pokemons: Vec<crate::structs::Pokemon> = serde_json::from_str(&contents)?;
+
+    for selection in selections {
+        // a random Pokemon is picked every time the team is printed
+        if selection.name == "random" {
+            continue;
+        }
+
+        // known names come back as slugs, anything else as it was typed
+        let Some(pokemon) = pokemons
+            .iter()
+            .find(|pokemon| pokemon.name == selection.name)
+        else {
+            let mut message = format!("Pokemon {} not found", selection.name);
+            let suggestions = crate::search::suggest(
+                &selection.name,
+                pokemons.iter().map(|pokemon| pokemon.name.as_str()),
+            );
+            if !suggestions.is_empty() {
+                message.push_str(&format!(
+                    "\n{}",
+                    crate::search::format_suggestions(&suggestions)
+                ));
+            }
+            return Err(message.into());
+        };
+
+        if let Some(form) = &selection.form {
+            if crate::search::find_match(form, pokemon.forms.iter().map(|f| f.as_str())).is_none() {
+                return Err(format!(
+                    "Form {} not found for {}\nHint: Do `rustmon list --forms {}` to see all forms.",
+                    form,
+                    pokemon.get_display_name(),
+                    pokemon.name
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn list(team: Option<&String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = crate::config::read_config()?;
+
+    match team {
+        Some(team) => match config.teams.get(team) {
+            Some(selectors) => print_team(team, selectors),
+            None => {
+                return Err(format!(
+                    "Team {} not found\nHint: Do `rustmon team list` to see all saved teams.",
+                    team
+                )
+                .into())
+            }
+        },
+        None => {
+            if config.teams.is_empty() {
+                println!("No teams saved yet.");
+            }
+            for (team, selectors) in &config.teams {
+                print_team(team, selectors);
+            }
+
+            if !config.aliases.is_empty() {
+                println!("Aliases:");
+                for (alias, selector) in &config.aliases {
+                    println!(" - {} = {}", alias, selector);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\nHint: Teams and aliases are saved in {}",
+        crate::config::config_file_path().display()
+    );
+
+    Ok(())
+}
+
+fn print_team(team: &str, selectors: &[String]) {
+    println!("{}:", team);
+    for selector in selectors {
+        println!(" - {}", selector);
+    }
+}
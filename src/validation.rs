@@ -14,6 +14,15 @@ pub fn validate_files() {
             std::process::exit(1);
         }
     };
+
+    // bring colorscripts from older versions up to date
+    match crate::migration::migrate_colorscripts() {
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Error: Failed to re-encode colorscripts: {e}");
+            std::process::exit(1);
+        }
+    };
 }
 
 fn validate_pokemon_json() -> Result<(), Box<dyn std::error::Error>> {